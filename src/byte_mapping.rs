@@ -1,10 +1,11 @@
-const NIL: char = '';
+/// The placeholder of the bytes without a printable char.
+pub const NIL: char = '';
 
 /// The mapping for [code page 850](https://en.wikipedia.org/wiki/Code_page_850)
 ///
 /// This code page is also known as `DOS/IBM-ASCII` and is used as the default
 /// code page by this library.
-pub const CODEPAGE_0850: &'static [char]  = &[
+pub const CODEPAGE_0850: &[char]  = &[
 //   0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
     NIL,  '☺',  '☻',  '♥',  '♦',  '♣',  '♠',  '•',  '◘',  '○',  '◙',  '♂',  '♀',  '♪',  '♫',  '☼', // 0
     '►',  '◄',  '↕',  '‼',  '¶',  '§',  '▬',  '↨',  '↑',  '↓',  '→',  '←',  '∟',  '↔',  '▲',  '▼', // 1
@@ -27,7 +28,7 @@ pub const CODEPAGE_0850: &'static [char]  = &[
 /// The mapping for [code page 1252](https://en.wikipedia.org/wiki/Code_page_1252)
 ///
 /// This code page is also known as `Latin 1 Windows` or `ANSI`.
-pub const CODEPAGE_1252: &'static [char] = &[
+pub const CODEPAGE_1252: &[char] = &[
 //   0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
     NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 0
     NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 1
@@ -38,7 +39,7 @@ pub const CODEPAGE_1252: &'static [char] = &[
     '`',  'a',  'b',  'c',  'd',  'e',  'f',  'g',  'h',  'i',  'j',  'k',  'l',  'm',  'n',  'o', // 6
     'p',  'q',  'r',  's',  't',  'u',  'v',  'w',  'x',  'y',  'z',  '{',  '|',  '}',  '~',  NIL, // 7
     '€',  NIL,  '‚',  'ƒ',  '„',  '…',  '†',  '‡',  'ˆ',  '‰',  'Š',  '‹',  'Œ',  NIL,  'Ž',  NIL, // 8
-    NIL,  '‘',  '’',  '“',  '”',  '•',  '–',  '—',  '˜',  '™',  'Š',  '›',  'œ',  NIL,  'ž',  'Ÿ', // 9
+    NIL,  '‘',  '’',  '“',  '”',  '•',  '–',  '—',  '˜',  '™',  'š',  '›',  'œ',  NIL,  'ž',  'Ÿ', // 9
     ' ',  '¡',  '¢',  '£',  '¤',  '¥',  '¦',  '§',  '¨',  '©',  'ª',  '«',  '¬',  NIL,  '®',  '¯', // A
     '°',  '±',  '²',  '³',  '´',  'µ',  '¶',  '·',  '¸',  '¹',  'º',  '»',  '¼',  '½',  '¾',  '¿', // B
     'À',  'Á',  'Â',  'Ã',  'Ä',  'Å',  'Æ',  'Ç',  'È',  'É',  'Ê',  'Ë',  'Ì',  'Í',  'Î',  'Ï', // C
@@ -47,11 +48,83 @@ pub const CODEPAGE_1252: &'static [char] = &[
     'ð',  'ñ',  'ò',  'ó',  'ô',  'õ',  'ö',  '÷',  'ø',  'ù',  'ú',  'û',  'ü',  'ý',  'þ',  'ÿ', // F
 ];
 
+/// The mapping for the [Mac OS Roman](https://en.wikipedia.org/wiki/Mac_OS_Roman) character set
+///
+/// This is the character set used by the classic Mac OS, e.g. in resource forks.
+pub const CODEPAGE_MAC_ROMAN: &[char] = &[
+//   0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 0
+    NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL,  NIL, // 1
+    ' ',  '!',  '"',  '#',  '$',  '%',  '&', '\'',  '(',  ')',  '*',  '+',  ',',  '-',  '.',  '/', // 2
+    '0',  '1',  '2',  '3',  '4',  '5',  '6',  '7',  '8',  '9',  ':',  ';',  '<',  '=',  '>',  '?', // 3
+    '@',  'A',  'B',  'C',  'D',  'E',  'F',  'G',  'H',  'I',  'J',  'K',  'L',  'M',  'N',  'O', // 4
+    'P',  'Q',  'R',  'S',  'T',  'U',  'V',  'W',  'X',  'Y',  'Z',  '[', '\\',  ']',  '^',  '_', // 5
+    '`',  'a',  'b',  'c',  'd',  'e',  'f',  'g',  'h',  'i',  'j',  'k',  'l',  'm',  'n',  'o', // 6
+    'p',  'q',  'r',  's',  't',  'u',  'v',  'w',  'x',  'y',  'z',  '{',  '|',  '}',  '~',  NIL, // 7
+    'Ä',  'Å',  'Ç',  'É',  'Ñ',  'Ö',  'Ü',  'á',  'à',  'â',  'ä',  'ã',  'å',  'ç',  'é',  'è', // 8
+    'ê',  'ë',  'í',  'ì',  'î',  'ï',  'ñ',  'ó',  'ò',  'ô',  'ö',  'õ',  'ú',  'ù',  'û',  'ü', // 9
+    '†',  '°',  '¢',  '£',  '§',  '•',  '¶',  'ß',  '®',  '©',  '™',  '´',  '¨',  '≠',  'Æ',  'Ø', // A
+    '∞',  '±',  '≤',  '≥',  '¥',  'µ',  '∂',  '∑',  '∏',  'π',  '∫',  'ª',  'º',  'Ω',  'æ',  'ø', // B
+    '¿',  '¡',  '¬',  '√',  'ƒ',  '≈',  '∆',  '«',  '»',  '…',  ' ',  'À',  'Ã',  'Õ',  'Œ',  'œ', // C
+    '–',  '—',  '“',  '”',  '‘',  '’',  '÷',  '◊',  'ÿ',  'Ÿ',  '⁄',  '€',  '‹',  '›',  'ﬁ',  'ﬂ', // D
+    '‡',  '·',  '‚',  '„',  '‰',  'Â',  'Ê',  'Á',  'Ë',  'È',  'Í',  'Î',  'Ï',  'Ì',  'Ó',  'Ô', // E
+    NIL,  'Ò',  'Ú',  'Û',  'Ù',  'ı',  'ˆ',  '˜',  '¯',  '˘',  '˙',  '˚',  '¸',  '˝',  '˛',  'ˇ', // F
+];
+
+/// The printable ASCII characters, with a `.` for every other byte.
+///
+/// This is the mapping used by tools like `hexdump -C` and `xxd`.
+pub const CODEPAGE_ASCII: &[char] = &[
+//   0     1     2     3     4     5     6     7     8     9     A     B     C     D     E     F
+    '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.', // 0
+    '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.', // 1
+    ' ',  '!',  '"',  '#',  '$',  '%',  '&', '\'',  '(',  ')',  '*',  '+',  ',',  '-',  '.',  '/', // 2
+    '0',  '1',  '2',  '3',  '4',  '5',  '6',  '7',  '8',  '9',  ':',  ';',  '<',  '=',  '>',  '?', // 3
+    '@',  'A',  'B',  'C',  'D',  'E',  'F',  'G',  'H',  'I',  'J',  'K',  'L',  'M',  'N',  'O', // 4
+    'P',  'Q',  'R',  'S',  'T',  'U',  'V',  'W',  'X',  'Y',  'Z',  '[', '\\',  ']',  '^',  '_', // 5
+    '`',  'a',  'b',  'c',  'd',  'e',  'f',  'g',  'h',  'i',  'j',  'k',  'l',  'm',  'n',  'o', // 6
+    'p',  'q',  'r',  's',  't',  'u',  'v',  'w',  'x',  'y',  'z',  '{',  '|',  '}',  '~',  '.', // 7
+    '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.', // 8
+    '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.', // 9
+    '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.', // A
+    '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.', // B
+    '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.', // C
+    '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.', // D
+    '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.', // E
+    '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.',  '.', // F
+];
+
+/// An alias for [CODEPAGE_1252](constant.CODEPAGE_1252.html) under its Windows name.
+///
+/// Compared to code page 850 it maps the 0x80–0x9F range to typographic characters such as the
+/// curly quotes and dashes.
+pub const CODEPAGE_WINDOWS_1252: &[char] = CODEPAGE_1252;
+
+/// Returns whether the byte is a printable ASCII character, including the space.
+pub fn is_printable_ascii(byte: u8) -> bool {
+    (0x20..0x7F).contains(&byte)
+}
+
+/// Returns the `cat -v` representation of a byte: `^@`..`^_` for the control characters, `^?`
+/// for DEL and the representation of the low 7 bits prefixed by `M-` for the high bytes.
+pub fn caret_notation(byte: u8) -> String {
+    match byte {
+        0x80..=0xFF => format!("M-{}", caret_notation(byte & 0x7F)),
+        0x7F => "^?".to_string(),
+        0x00..=0x1F => format!("^{}", (byte + 0x40) as char),
+        _ => (byte as char).to_string(),
+    }
+}
+
 fn contains(byte: u8, codepage: &[char]) -> bool {
     (byte as usize) < codepage.len()
 }
 
 /// Returns a byte's character representation given a specific codepage
+///
+/// The byte is used as an index into `codepage`. Bytes beyond the end of the codepage don't
+/// panic, they are mapped to the same placeholder as the unprintable entries of the built-in
+/// code pages (`'\u{E000}'`, from the Unicode private use area).
 pub fn as_char(byte: u8, codepage: &[char]) -> char {
     if !contains(byte, codepage) {
         return NIL;
@@ -59,3 +132,84 @@ pub fn as_char(byte: u8, codepage: &[char]) -> char {
 
     codepage[byte as usize]
 }
+
+/// Returns whether `c` occupies a single terminal column.
+///
+/// This is an approximation of the Unicode display width that rejects control characters,
+/// zero width and combining characters as well as the wide and full width ranges.
+pub fn is_single_width(c: char) -> bool {
+    !matches!(c as u32,
+        0x00..=0x1F | 0x7F..=0x9F
+        | 0x0300..=0x036F | 0x200B..=0x200F | 0xFE00..=0xFE0F
+        | 0x1100..=0x115F | 0x2E80..=0x303E | 0x3041..=0x33FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60 | 0xFFE0..=0xFFE6 | 0x1F300..=0x1F64F | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD)
+}
+
+/// Checks that every char of `codepage` occupies a single terminal column, so the char panel
+/// stays aligned.
+///
+/// Returns the index of the first char that is wider or narrower than one column.
+pub fn validate_single_width(codepage: &[char]) -> Result<(), usize> {
+    match codepage.iter().position(|&c| !is_single_width(c)) {
+        Some(index) => Err(index),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_are_mapped_using_the_codepage() {
+        assert_eq!('A', as_char(0x41, CODEPAGE_0850));
+        assert_eq!('☺', as_char(0x01, CODEPAGE_0850));
+        assert_eq!('ß', as_char(0xE1, CODEPAGE_0850));
+    }
+
+    #[test]
+    fn bytes_beyond_the_codepage_map_to_the_placeholder() {
+        assert_eq!('\u{E000}', as_char(0x05, &['a', 'b']));
+    }
+
+    #[test]
+    fn windows_1252_maps_the_smart_double_quotes() {
+        assert_eq!(256, CODEPAGE_WINDOWS_1252.len());
+        assert_eq!('“', as_char(0x93, CODEPAGE_WINDOWS_1252));
+        assert_eq!('”', as_char(0x94, CODEPAGE_WINDOWS_1252));
+        assert_eq!('š', as_char(0x9A, CODEPAGE_WINDOWS_1252));
+    }
+
+    #[test]
+    fn the_builtin_codepages_are_single_width() {
+        assert_eq!(Ok(()), validate_single_width(CODEPAGE_0850));
+        assert_eq!(Ok(()), validate_single_width(CODEPAGE_ASCII));
+        assert_eq!(Ok(()), validate_single_width(CODEPAGE_1252));
+        assert_eq!(Ok(()), validate_single_width(CODEPAGE_MAC_ROMAN));
+    }
+
+    #[test]
+    fn a_full_width_char_is_detected() {
+        assert_eq!(Err(2), validate_single_width(&['a', 'b', 'Ａ', '漢']));
+        assert_eq!(Err(1), validate_single_width(&['a', '\u{0301}']));
+    }
+
+    #[test]
+    fn control_and_high_bytes_use_the_caret_notation() {
+        assert_eq!("^A", caret_notation(0x01));
+        assert_eq!("^?", caret_notation(0x7F));
+        assert_eq!("A", caret_notation(0x41));
+        assert_eq!("M-A", caret_notation(0xC1));
+        assert_eq!("M-^@", caret_notation(0x80));
+    }
+
+    #[test]
+    fn mac_roman_maps_the_high_range() {
+        assert_eq!(256, CODEPAGE_MAC_ROMAN.len());
+        assert_eq!('•', as_char(0xA5, CODEPAGE_MAC_ROMAN));
+        assert_eq!('π', as_char(0xB9, CODEPAGE_MAC_ROMAN));
+        assert_eq!('ˇ', as_char(0xFF, CODEPAGE_MAC_ROMAN));
+    }
+}
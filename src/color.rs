@@ -0,0 +1,87 @@
+use std::fmt::{Display, Formatter, Result};
+
+
+/// A terminal color, emitted as an ANSI escape code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    /// A color of the 256 color palette.
+    Ansi256(u8),
+    /// A 24-bit color, given by its red, green and blue components.
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// Writes the SGR parameters that select the color as background.
+    fn fmt_background(&self, f: &mut Formatter) -> Result {
+        match *self {
+            Color::Black => write!(f, "40"),
+            Color::Red => write!(f, "41"),
+            Color::Green => write!(f, "42"),
+            Color::Yellow => write!(f, "43"),
+            Color::Blue => write!(f, "44"),
+            Color::Magenta => write!(f, "45"),
+            Color::Cyan => write!(f, "46"),
+            Color::White => write!(f, "47"),
+            Color::Ansi256(index) => write!(f, "48;5;{}", index),
+            Color::Rgb(red, green, blue) => write!(f, "48;2;{};{};{}", red, green, blue),
+        }
+    }
+
+    /// Writes the SGR parameters that select the color as foreground.
+    fn fmt_foreground(&self, f: &mut Formatter) -> Result {
+        match *self {
+            Color::Black => write!(f, "30"),
+            Color::Red => write!(f, "31"),
+            Color::Green => write!(f, "32"),
+            Color::Yellow => write!(f, "33"),
+            Color::Blue => write!(f, "34"),
+            Color::Magenta => write!(f, "35"),
+            Color::Cyan => write!(f, "36"),
+            Color::White => write!(f, "37"),
+            Color::Ansi256(index) => write!(f, "38;5;{}", index),
+            Color::Rgb(red, green, blue) => write!(f, "38;2;{};{};{}", red, green, blue),
+        }
+    }
+}
+
+/// Writes `value`, wrapped in the escape codes for `color` when a color is given.
+pub fn fmt_colored<T: Display>(f: &mut Formatter, color: Option<Color>, value: T) -> Result {
+    fmt_styled(f, color, None, value)
+}
+
+/// Writes `value`, wrapped in the escape codes for a dim intensity.
+pub fn fmt_dimmed<T: Display>(f: &mut Formatter, value: T) -> Result {
+    write!(f, "\x1b[2m{}\x1b[0m", value)
+}
+
+/// Writes `value`, wrapped in the escape codes for the foreground and background colors that are
+/// given.
+pub fn fmt_styled<T: Display>(f: &mut Formatter, fg: Option<Color>, bg: Option<Color>, value: T) -> Result {
+    if fg.is_none() && bg.is_none() {
+        return write!(f, "{}", value);
+    }
+
+    write!(f, "\x1b[")?;
+
+    if let Some(fg) = fg {
+        fg.fmt_foreground(f)?;
+    }
+
+    if let Some(bg) = bg {
+        if fg.is_some() {
+            write!(f, ";")?;
+        }
+
+        bg.fmt_background(f)?;
+    }
+
+    write!(f, "m{}\x1b[0m", value)
+}
@@ -0,0 +1,134 @@
+use std::cmp;
+use std::ops::Range;
+
+use color::Color;
+use format::{HexView, HexViewBuilder};
+
+
+/// Dumps the differences of `old` and `new` like a unified diff.
+///
+/// Identical rows are printed once with a two space indent, a row that differs is printed from
+/// `old` with a leading `-` followed by the row from `new` with a leading `+`. The differing bytes
+/// are colored red, bytes beyond the end of the shorter buffer count as different.
+pub fn diff_files(old: &[u8], new: &[u8]) -> String {
+    let changes = changed_ranges(old, new);
+    let old_view = highlighted_view(old, &changes);
+    let new_view = highlighted_view(new, &changes);
+    let mut lines = Vec::new();
+
+    for index in 0.. {
+        match (old_view.render_row(index), new_view.render_row(index)) {
+            (None, None) => break,
+            (Some(old_row), Some(new_row)) if old_row == new_row => lines.push(format!("  {}", old_row)),
+            (old_row, new_row) => {
+                lines.extend(old_row.map(|row| format!("- {}", row)));
+                lines.extend(new_row.map(|row| format!("+ {}", row)));
+            }
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Dumps `a` and `b` interleaved for XOR analysis, every row of 16 bytes is printed as three lines
+/// with the bytes of `a`, the bytes of `b` and their XOR stacked in the same columns.
+///
+/// The shorter buffer is padded with blank columns, which have no XOR either.
+pub fn interleave(a: &[u8], b: &[u8]) -> String {
+    const ROW_WIDTH: usize = 16;
+
+    let len = cmp::max(a.len(), b.len());
+    let mut lines = Vec::new();
+
+    for start in (0..cmp::max(1, len)).step_by(ROW_WIDTH) {
+        let end = cmp::min(len, start + ROW_WIDTH);
+        let columns = |byte: &dyn Fn(usize) -> Option<u8>| {
+            (start..end)
+                .map(|offset| byte(offset).map_or("  ".to_string(), |byte| format!("{:02X}", byte)))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        lines.push(format!("{:08X}  a  {}", start, columns(&|offset| a.get(offset).cloned())));
+        lines.push(format!("{:8}  b  {}", "", columns(&|offset| b.get(offset).cloned())));
+        lines.push(format!("{:8}  ^  {}", "", columns(&|offset| match (a.get(offset), b.get(offset)) {
+            (Some(&a), Some(&b)) => Some(a ^ b),
+            _ => None,
+        })));
+    }
+
+    lines.iter().map(|line| line.trim_end()).collect::<Vec<_>>().join("\n")
+}
+
+/// Returns the runs of offsets at which the bytes of both buffers differ.
+pub fn changed_ranges(old: &[u8], new: &[u8]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+
+    for offset in 0..cmp::max(old.len(), new.len()) {
+        if old.get(offset) == new.get(offset) {
+            continue;
+        }
+
+        match ranges.last_mut() {
+            Some(range) if range.end == offset => range.end += 1,
+            _ => ranges.push(offset..offset + 1),
+        }
+    }
+
+    ranges
+}
+
+fn highlighted_view<'a>(data: &'a [u8], changes: &[Range<usize>]) -> HexView<'a> {
+    changes.iter()
+        .fold(HexViewBuilder::new(data).use_color(true), |builder, range| builder.highlight(range.clone(), Color::Red))
+        .finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_differing_bytes_are_marked() {
+        let old = [b'a'; 32];
+        let mut new = old;
+        new[3] = b'b';
+        new[4] = b'c';
+        new[30] = b'd';
+
+        let result = diff_files(&old, &new);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("- 00000000  61 61 61 \x1b[31m61\x1b[0m \x1b[31m61\x1b[0m 61 61 61 61 61 61 61 61 61 61 61  | aaa\x1b[31ma\x1b[0m\x1b[31ma\x1b[0maaaaaaaaaaa |"), lines.next());
+        assert_eq!(Some("+ 00000000  61 61 61 \x1b[31m62\x1b[0m \x1b[31m63\x1b[0m 61 61 61 61 61 61 61 61 61 61 61  | aaa\x1b[31mb\x1b[0m\x1b[31mc\x1b[0maaaaaaaaaaa |"), lines.next());
+        assert_eq!(Some("- 00000010  61 61 61 61 61 61 61 61 61 61 61 61 61 61 \x1b[31m61\x1b[0m 61  | aaaaaaaaaaaaaa\x1b[31ma\x1b[0ma |"), lines.next());
+        assert_eq!(Some("+ 00000010  61 61 61 61 61 61 61 61 61 61 61 61 61 61 \x1b[31m64\x1b[0m 61  | aaaaaaaaaaaaaa\x1b[31md\x1b[0ma |"), lines.next());
+        assert_eq!(None, lines.next());
+        assert_eq!(2 * 2 * 3, result.matches("\x1b[31m").count());
+    }
+
+    #[test]
+    fn the_xor_is_stacked_under_both_buffers() {
+        let result = interleave(&[0x41, 0x42, 0xFF, 0x00], &[0x41, 0x40, 0x0F, 0x80, 0x01]);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00000000  a  41 42 FF 00"), lines.next());
+        assert_eq!(Some("          b  41 40 0F 80 01"), lines.next());
+        assert_eq!(Some("          ^  00 02 F0 80"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn identical_rows_are_printed_once() {
+        let old: Vec<u8> = (0x41..0x41 + 20).collect();
+        let new: Vec<u8> = (0x41..0x41 + 17).collect();
+
+        let result = diff_files(&old, &new);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("  00000000  41 42 43 44 45 46 47 48 49 4A 4B 4C 4D 4E 4F 50  | ABCDEFGHIJKLMNOP |"), lines.next());
+        assert!(lines.next().unwrap().starts_with("- 00000010  51 \x1b[31m52\x1b[0m"));
+        assert!(lines.next().unwrap().starts_with("+ 00000010  51    "));
+        assert_eq!(None, lines.next());
+    }
+}
@@ -1,25 +1,351 @@
-use std::fmt::{Formatter, Result};
+use std::borrow::Cow;
+use std::fmt::{Formatter, Result, Write};
+use std::ops::{Bound, Range, RangeBounds};
 use std;
 
 use byte_mapping;
+use color::{self, Color};
+use diff;
 
+mod export;
+mod field;
+mod fragments;
+mod multi;
+mod owned;
+mod reader;
+mod records;
+
+pub use self::field::FieldView;
+pub use self::multi::MultiHexView;
+pub use self::owned::OwnedHexView;
+pub use self::records::RecordView;
+
+use self::fragments::Fragments;
+
+const ADDRESS_WIDTH: usize = 8;
+
+/// The numeral system used to display a number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Radix {
+    Binary,
+    Octal,
+    Decimal,
+    Hexadecimal,
+}
+
+/// The byte order of multi-byte values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+/// The side of a line a column is printed on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Position {
+    Left,
+    Right,
+}
+
+/// The checksum shown for every row by
+/// [show_row_checksum](struct.HexViewBuilder.html#method.show_row_checksum).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumKind {
+    /// The XOR of all bytes.
+    Xor,
+    /// The wrapping sum of all bytes.
+    Sum8,
+    /// The parity of all bits, `1` for an odd number of set bits.
+    Parity,
+}
+
+/// The colors of a range of bytes, added with
+/// [highlight_styled](struct.HexViewBuilder.html#method.highlight_styled).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Highlight {
+    /// The offsets of the bytes, relative to the start of the data.
+    pub range: Range<usize>,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+}
+
+/// Decodes the start of the bytes into the number of bytes it consumed and a text.
+type DecodeFn = fn(&[u8]) -> Option<(usize, String)>;
+
+/// Returns the colors of the high and low nibble of a byte, given its offset and value.
+type NibbleColorFn = fn(usize, u8) -> (Option<Color>, Option<Color>);
 
 /// The HexView struct represents the configuration of how to display the data.
+#[derive(Clone)]
 pub struct HexView<'a> {
     address_offset: usize,
+    relative_addresses: bool,
+    address_position: Position,
     codepage: &'a [char],
-    data: &'a [u8],
+    utf16_char_panel: Option<Endian>,
+    sanitize_char_panel: bool,
+    caret_notation: bool,
+    char_row_width: Option<usize>,
+    read_buffer_rows: usize,
+    non_printable_char: char,
+    padding_marker: char,
+    data: Fragments<'a>,
+    limit: Option<usize>,
+    omit_message: Option<fn(usize) -> String>,
     row_width: usize,
+    width_changes: Vec<(usize, usize)>,
+    stride: usize,
+    group_size: usize,
+    word_size: usize,
+    word_endian: Endian,
+    mirror_char_panel: bool,
+    group_gap: usize,
+    elements_per_row: Option<usize>,
+    address_radix: Radix,
+    address_every: usize,
+    address_grouping: Option<char>,
+    min_address_width: usize,
+    segment_shift: Option<u32>,
+    human_address: bool,
+    address_suffix: &'a str,
+    address_uppercase: bool,
+    byte_uppercase: bool,
+    hex_prefix: Option<&'a str>,
+    stacked_signed_decimal: bool,
+    panel_divider: &'a str,
+    begin_pad_fill: &'a str,
+    align_char_panel: bool,
+    max_width: Option<usize>,
+    char_panel_column: Option<usize>,
+    align_addresses: bool,
+    align_to: usize,
+    empty_shows_address: bool,
+    show_row_numbers: bool,
+    show_hex_row_index: bool,
+    show_end_address: bool,
+    show_percentage: bool,
+    total_size: Option<usize>,
+    compact_last_row: bool,
+    od_layout: bool,
+    html_row_anchors: bool,
+    show_class_bar: bool,
+    show_reversed_column: bool,
+    inline_ascii: bool,
+    row_checksum: Option<ChecksumKind>,
+    squeeze: bool,
+    squeeze_count: bool,
+    show_final_offset: bool,
+    show_codepage_legend: bool,
+    hide_empty_char_panel: bool,
+    rtl: bool,
+    tab_separated: bool,
+    cell_borders: bool,
+    title: Option<&'a str>,
+    line_continuation: bool,
+    line_prefix: &'a str,
+    indent_level: usize,
+    indent_width: usize,
+    strings_min_len: Option<usize>,
+    show_header: bool,
+    header_tick: usize,
+    bitfield: Option<Range<usize>>,
+    tags: Vec<(usize, char)>,
+    gaps: Vec<(usize, usize)>,
+    underlines: Vec<Range<usize>>,
+    focus: Vec<Range<usize>>,
+    diff_against_previous: bool,
+    use_color: bool,
+    highlights: Vec<Highlight>,
+    color_fn: Option<fn(usize, u8) -> Option<Color>>,
+    nibble_color_fn: Option<NibbleColorFn>,
+    decode_fn: Option<DecodeFn>,
+    layout: Option<Layout>,
 }
 
 impl<'a> HexView<'a> {
-    pub fn new(data: &[u8]) -> HexView {
+    pub fn new(data: &[u8]) -> HexView<'_> {
         HexView {
             address_offset: 0,
-            codepage: &byte_mapping::CODEPAGE_0850,
-            data: data,
+            relative_addresses: false,
+            address_position: Position::Left,
+            codepage: byte_mapping::CODEPAGE_0850,
+            utf16_char_panel: None,
+            sanitize_char_panel: false,
+            caret_notation: false,
+            char_row_width: None,
+            read_buffer_rows: 1,
+            non_printable_char: '.',
+            padding_marker: ' ',
+            data: Fragments::new(&[data]),
+            limit: None,
+            omit_message: None,
             row_width: 16,
+            width_changes: Vec::new(),
+            stride: 1,
+            group_size: 0,
+            word_size: 1,
+            word_endian: Endian::Big,
+            mirror_char_panel: false,
+            group_gap: 2,
+            elements_per_row: None,
+            address_radix: Radix::Hexadecimal,
+            address_every: 1,
+            address_grouping: None,
+            min_address_width: 0,
+            segment_shift: None,
+            human_address: false,
+            address_suffix: "  ",
+            address_uppercase: true,
+            byte_uppercase: true,
+            hex_prefix: None,
+            stacked_signed_decimal: false,
+            panel_divider: "| ",
+            begin_pad_fill: "  ",
+            align_char_panel: true,
+            max_width: None,
+            char_panel_column: None,
+            align_addresses: true,
+            align_to: 0,
+            empty_shows_address: true,
+            show_row_numbers: false,
+            show_hex_row_index: false,
+            show_end_address: false,
+            show_percentage: false,
+            total_size: None,
+            compact_last_row: false,
+            od_layout: false,
+            html_row_anchors: false,
+            show_class_bar: false,
+            show_reversed_column: false,
+            inline_ascii: false,
+            row_checksum: None,
+            squeeze: false,
+            squeeze_count: false,
+            show_final_offset: false,
+            show_codepage_legend: false,
+            hide_empty_char_panel: false,
+            rtl: false,
+            tab_separated: false,
+            cell_borders: false,
+            title: None,
+            line_continuation: false,
+            line_prefix: "",
+            indent_level: 0,
+            indent_width: 4,
+            strings_min_len: None,
+            show_header: false,
+            header_tick: 0,
+            bitfield: None,
+            tags: Vec::new(),
+            gaps: Vec::new(),
+            underlines: Vec::new(),
+            focus: Vec::new(),
+            diff_against_previous: false,
+            use_color: false,
+            highlights: Vec::new(),
+            color_fn: None,
+            nibble_color_fn: None,
+            decode_fn: None,
+            layout: None,
+        }
+    }
+
+    /// Creates a view of several fragments that are treated as one contiguous stream of bytes
+    /// starting at `address_offset`, without copying them.
+    pub fn from_slices(fragments: &[&'a [u8]], address_offset: usize) -> HexView<'a> {
+        HexViewBuilder::from_slices(fragments)
+            .address_offset(address_offset)
+            .finish()
+    }
+
+    /// Returns the printed addresses of the shown data, from the first byte to just past the last
+    /// byte before the [limit](struct.HexViewBuilder.html#method.limit).
+    pub fn address_range(&self) -> Range<u64> {
+        let start = self.shown_address_offset() as u64;
+
+        start..start.saturating_add(self.shown_len() as u64)
+    }
+
+    /// Formats the view with the bytes that differ from `previous` colored red, for a monitor that
+    /// dumps the same region over and over. The caller keeps the previous snapshot.
+    pub fn diff_render(&self, previous: &[u8]) -> String {
+        let mut view = self.clone();
+        view.use_color = true;
+
+        for range in diff::changed_ranges(previous, &self.data.joined()) {
+            view.highlights.push(Highlight { range, fg: Some(Color::Red), bg: None });
+        }
+
+        format!("{}", view)
+    }
+
+    /// Returns the number of bytes without a printable glyph in the codepage, which are shown as a
+    /// placeholder (`'\u{E000}'` or `.`) in the char panel.
+    pub fn non_printable_count(&self) -> usize {
+        self.data.iter().filter(|&byte| self.is_non_printable(byte)).count()
+    }
+
+    /// Splits the view into pages of `rows_per_page` rows, each with the configuration of this view
+    /// and the address offset of its first byte, so they can be formatted independently.
+    ///
+    /// A view with a zero row width is a single page.
+    pub fn pages(&self, rows_per_page: usize) -> impl Iterator<Item = HexView<'a>> + '_ {
+        let data = self.shown_data();
+        let (begin_padding, page_span) = if self.row_width == 0 {
+            (0, std::cmp::max(1, data.len()))
+        } else {
+            (self.begin_padding() * self.stride, std::cmp::max(1, rows_per_page) * self.row_width * self.stride)
+        };
+        let page_count = std::cmp::max(1, (begin_padding + data.len()).div_ceil(page_span));
+
+        (0..page_count).map(move |page| {
+            let start = (page * page_span).saturating_sub(begin_padding);
+            let end = std::cmp::min(data.len(), ((page + 1) * page_span).saturating_sub(begin_padding));
+
+            let mut view = self.clone();
+            view.data = data.range(start..end);
+            view.address_offset += start;
+            view.limit = None;
+            view
+        })
+    }
+
+    /// Returns whether the data of both views differs, the display configuration is ignored.
+    pub fn differs_from(&self, other: &HexView) -> bool {
+        self.data.len() != other.data.len() || !self.data.iter().eq(other.data.iter())
+    }
+
+    /// Returns the length in bytes of the formatted view, including the header and footer.
+    ///
+    /// The view is formatted into a counter, so no memory is allocated for the output.
+    pub fn output_len(&self) -> usize {
+        let mut counter = LengthCounter(0);
+        let _ = write!(counter, "{}", self);
+        counter.0
+    }
+
+    /// Calls `callback` with every line of the formatted view, in order.
+    ///
+    /// The lines are formatted into a single buffer that is reused for all lines, so no memory is
+    /// allocated for the whole output.
+    pub fn for_each_line<F: FnMut(&str)>(&self, callback: F) {
+        let mut splitter = LineSplitter { line: String::new(), callback };
+        let _ = write!(splitter, "{}", self);
+        (splitter.callback)(&splitter.line);
+    }
+
+    /// Returns the formatted display row at `row_index`, or `None` when there is no such row.
+    ///
+    /// The first row is the (possibly partial) row holding the first byte, the header and footer
+    /// are not counted as rows.
+    pub fn render_row(&self, row_index: usize) -> Option<String> {
+        if self.row_width == 0 {
+            return None;
         }
+
+        self.rows()
+            .nth(row_index)
+            .map(|row| format!("{}", RowDisplay { view: self, row }))
     }
 }
 
@@ -28,234 +354,3853 @@ pub struct HexViewBuilder<'a> {
     hex_view: HexView<'a>,
 }
 
-impl<'a> HexViewBuilder<'a> {
-    pub fn new(data: &[u8]) -> HexViewBuilder {
-        HexViewBuilder {
-            hex_view: HexView::new(&data)
-        }
+impl<'a> HexViewBuilder<'a> {
+    pub fn new(data: &[u8]) -> HexViewBuilder<'_> {
+        HexViewBuilder {
+            hex_view: HexView::new(data)
+        }
+    }
+
+    /// Creates a builder for a view of several fragments that are treated as one contiguous
+    /// stream of bytes, rows may span the boundaries of the fragments.
+    pub fn from_slices(fragments: &[&'a [u8]]) -> HexViewBuilder<'a> {
+        let mut hex_view = HexView::new(&[]);
+        hex_view.data = Fragments::new(fragments);
+
+        HexViewBuilder { hex_view }
+    }
+
+    /// Appends `fragment` to the data, after the fragments and [gaps](#method.gap) added so far.
+    pub fn append(mut self, fragment: &'a [u8]) -> HexViewBuilder<'a> {
+        self.hex_view.data.push(fragment);
+        self
+    }
+
+    /// Adds a hole of `len` bytes after the data added so far, e.g. an unmapped range of a memory
+    /// map. The hole is shown as a `... gap of N bytes ...` line and the addresses of the bytes
+    /// [appended](#method.append) after it are advanced by `len`.
+    ///
+    /// The data on both sides of a gap is formatted like the regions of a
+    /// [MultiHexView](struct.MultiHexView.html), the title and header are only printed once. A
+    /// gap in front of all data is shown below them.
+    pub fn gap(mut self, len: usize) -> HexViewBuilder<'a> {
+        let offset = self.hex_view.data.len();
+
+        match self.hex_view.gaps.last_mut() {
+            Some(gap) if gap.0 == offset => gap.1 += len,
+            _ if len != 0 => self.hex_view.gaps.push((offset, len)),
+            _ => {}
+        }
+
+        self
+    }
+
+    pub fn address_offset(mut self, offset: usize) -> HexViewBuilder<'a> {
+        self.hex_view.address_offset = offset;
+        self
+    }
+
+    /// Prints the address column on the `Left` of the line (the default) or on the `Right`, after
+    /// the char panel.
+    pub fn address_position(mut self, position: Position) -> HexViewBuilder<'a> {
+        self.hex_view.address_position = position;
+        self
+    }
+
+    /// Prints the addresses relative to the start of the data, as if the address offset was 0.
+    ///
+    /// The absolute address offset is printed in the address column of the
+    /// [header](#method.show_header).
+    pub fn relative_addresses(mut self, relative: bool) -> HexViewBuilder<'a> {
+        self.hex_view.relative_addresses = relative;
+        self
+    }
+
+    pub fn codepage<'b: 'a>(mut self, codepage: &'b [char]) -> HexViewBuilder<'a> {
+        self.hex_view.codepage = codepage;
+        self
+    }
+
+    /// Decodes the char panel as UTF-16 code units of the given byte order.
+    ///
+    /// Each character is shown under the first byte of its pair, the second column stays blank.
+    /// Unpaired trailing bytes and surrogates are shown as the replacement character.
+    pub fn utf16_char_panel(mut self, endian: Endian) -> HexViewBuilder<'a> {
+        self.hex_view.utf16_char_panel = Some(endian);
+        self
+    }
+
+    /// Replaces the control, combining, zero width and wide chars of the char panel with the
+    /// [non_printable_char](#method.non_printable_char), so they can't break the alignment
+    /// whatever the codepage is. This includes the chars of the
+    /// [UTF-16 char panel](#method.utf16_char_panel), the [caret notation](#method.caret_notation)
+    /// is ASCII only and needs no sanitizing.
+    pub fn sanitize_char_panel(mut self, sanitize: bool) -> HexViewBuilder<'a> {
+        self.hex_view.sanitize_char_panel = sanitize;
+        self
+    }
+
+    /// Fills the padding columns of the char panel with `marker`, a space by default, to tell the
+    /// layout padding apart from space bytes at the end of the data.
+    pub fn mark_padding(mut self, marker: char) -> HexViewBuilder<'a> {
+        self.hex_view.padding_marker = marker;
+        self
+    }
+
+    /// Shows the bytes of the char panel like `cat -v`: `^A` for the control characters, `^?` for
+    /// DEL and `M-` prefixed forms for the bytes above `0x7F`. The codepage is ignored.
+    ///
+    /// Every byte takes two columns of the char panel, or four when the data holds high bytes.
+    pub fn caret_notation(mut self, caret_notation: bool) -> HexViewBuilder<'a> {
+        self.hex_view.caret_notation = caret_notation;
+        self
+    }
+
+    /// Sets the placeholder of the chars removed by [sanitize_char_panel](#method.sanitize_char_panel),
+    /// `.` by default.
+    pub fn non_printable_char(mut self, placeholder: char) -> HexViewBuilder<'a> {
+        self.hex_view.non_printable_char = placeholder;
+        self
+    }
+
+    /// Like [codepage](#method.codepage), but fails with the index of the first char that does
+    /// not occupy a single terminal column, as it would break the alignment of the char panel.
+    pub fn try_codepage<'b: 'a>(self, codepage: &'b [char]) -> std::result::Result<HexViewBuilder<'a>, usize> {
+        byte_mapping::validate_single_width(codepage)?;
+        Ok(self.codepage(codepage))
+    }
+
+    pub fn row_width(mut self, width: usize) -> HexViewBuilder<'a> {
+        self.hex_view.row_width = width;
+        self
+    }
+
+    /// Wraps the rows from offset `at_offset` of the data on at `new_width` bytes, for data with
+    /// sections of different layouts.
+    ///
+    /// A row never spans the offset of a change. The panels are laid out for the widest row, the
+    /// narrower rows are padded on the right. A zero `new_width` is treated as 1, and a row with
+    /// begin padding is widened to hold at least one byte.
+    pub fn width_change(mut self, at_offset: usize, new_width: usize) -> HexViewBuilder<'a> {
+        self.hex_view.width_changes.retain(|&(offset, _)| offset != at_offset);
+        self.hex_view.width_changes.push((at_offset, std::cmp::max(1, new_width)));
+        self.hex_view.width_changes.sort_by_key(|&(offset, _)| offset);
+        self
+    }
+
+    /// Shows `width` bytes per line in the char panel, independent of the `row_width` of the hex
+    /// panel, to read text faster next to a narrow hex panel.
+    ///
+    /// The width is rounded down to a multiple of the row width. The char panel is then only
+    /// printed on every `width / row_width`-th row, starting at its first byte, and left blank on
+    /// the rows in between. Widths below the row width are ignored.
+    pub fn char_row_width(mut self, width: usize) -> HexViewBuilder<'a> {
+        self.hex_view.char_row_width = Some(width);
+        self
+    }
+
+    /// Sets the number of rows read at once by [dump_reader](#method.dump_reader), 1 by default.
+    ///
+    /// Larger buffers take fewer reads at the cost of memory.
+    pub fn read_buffer_rows(mut self, rows: usize) -> HexViewBuilder<'a> {
+        self.hex_view.read_buffer_rows = rows;
+        self
+    }
+
+    /// Only shows every `stride`th byte of the data, for a coarse overview of a large buffer.
+    ///
+    /// The addresses keep referring to the original offsets, so they advance by `stride` per byte.
+    pub fn stride(mut self, stride: usize) -> HexViewBuilder<'a> {
+        self.hex_view.stride = std::cmp::max(1, stride);
+        self
+    }
+
+    /// Only dumps the first `limit` bytes, followed by a note with the number of omitted bytes.
+    pub fn limit(mut self, limit: usize) -> HexViewBuilder<'a> {
+        self.hex_view.limit = Some(limit);
+        self
+    }
+
+    /// Replaces the note below a [limited](#method.limit) dump, `omit_message` receives the
+    /// number of omitted bytes.
+    pub fn omit_message(mut self, omit_message: fn(usize) -> String) -> HexViewBuilder<'a> {
+        self.hex_view.omit_message = Some(omit_message);
+        self
+    }
+
+    /// Splits the hex panel into groups of `size` bytes, 0 (the default) disables the grouping.
+    pub fn group_size(mut self, size: usize) -> HexViewBuilder<'a> {
+        self.hex_view.group_size = size;
+        self
+    }
+
+    /// Splits the hex panel into words of `size` bytes whose byte order is set by the
+    /// [word_endian](#method.word_endian). The words are grouped like
+    /// [group_size](#method.group_size), unless a group size is set.
+    pub fn word_size(mut self, size: usize) -> HexViewBuilder<'a> {
+        self.hex_view.word_size = size;
+        self
+    }
+
+    /// Sets the byte order of the [words](#method.word_size), `Big` (the memory order) by default.
+    ///
+    /// With `Little`, the bytes of every word are shown in reverse, so the hex panel reads as the
+    /// values of the words.
+    pub fn word_endian(mut self, endian: Endian) -> HexViewBuilder<'a> {
+        self.hex_view.word_endian = endian;
+        self
+    }
+
+    /// Reverses the bytes of the char panel per word too, like the hex panel with a `Little`
+    /// [word_endian](#method.word_endian), so both panels stay consistent.
+    pub fn mirror_char_panel(mut self, mirror: bool) -> HexViewBuilder<'a> {
+        self.hex_view.mirror_char_panel = mirror;
+        self
+    }
+
+    /// Sets the number of spaces between two groups of the hex panel, 2 by default.
+    pub fn group_gap(mut self, gap: usize) -> HexViewBuilder<'a> {
+        self.hex_view.group_gap = gap;
+        self
+    }
+
+    /// Sets the radix of the address column, hexadecimal by default.
+    ///
+    /// The address is zero padded to at least 8 digits of the chosen radix, more when the largest
+    /// address needs them.
+    pub fn address_radix(mut self, radix: Radix) -> HexViewBuilder<'a> {
+        self.hex_view.address_radix = radix;
+        self
+    }
+
+    /// Separates the thousands of a [decimal](enum.Radix.html#variant.Decimal) address with
+    /// `separator`, e.g. `1,048,576`. The grouped addresses are right aligned instead of zero padded.
+    pub fn address_grouping(mut self, separator: Option<char>) -> HexViewBuilder<'a> {
+        self.hex_view.address_grouping = separator;
+        self
+    }
+
+    /// The text written directly after the address, two spaces by default.
+    pub fn address_suffix<'b: 'a>(mut self, suffix: &'b str) -> HexViewBuilder<'a> {
+        self.hex_view.address_suffix = suffix;
+        self
+    }
+
+    /// Prints the addresses as `SSSS:OOOO` like the x86 real mode debuggers, the address is split
+    /// into a 16 bit offset and a segment that is the remainder shifted right by `segment_shift`.
+    ///
+    /// This takes precedence over the [address_radix](#method.address_radix).
+    pub fn segmented_address(mut self, segment_shift: u32) -> HexViewBuilder<'a> {
+        self.hex_view.segment_shift = Some(segment_shift);
+        self
+    }
+
+    /// Prints the addresses as rounded sizes with binary prefixes, like `1.0K` or `2.5M`, for a
+    /// rough overview of large dumps.
+    ///
+    /// This takes precedence over the [address_radix](#method.address_radix) and the
+    /// [segmented_address](#method.segmented_address).
+    pub fn human_address(mut self, human_address: bool) -> HexViewBuilder<'a> {
+        self.hex_view.human_address = human_address;
+        self
+    }
+
+    /// Only prints the address on every `rows`-th row, the other rows get a blank address column.
+    pub fn address_every(mut self, rows: usize) -> HexViewBuilder<'a> {
+        self.hex_view.address_every = rows;
+        self
+    }
+
+    /// Uses uppercase hexadecimal digits (the default) or lowercase ones, for both the addresses
+    /// and the bytes.
+    pub fn uppercase(self, uppercase: bool) -> HexViewBuilder<'a> {
+        self.address_uppercase(uppercase).byte_uppercase(uppercase)
+    }
+
+    /// Uses uppercase hexadecimal digits (the default) or lowercase ones for the addresses.
+    pub fn address_uppercase(mut self, uppercase: bool) -> HexViewBuilder<'a> {
+        self.hex_view.address_uppercase = uppercase;
+        self
+    }
+
+    /// Uses uppercase hexadecimal digits (the default) or lowercase ones for the bytes, the
+    /// column indices and the exports.
+    pub fn byte_uppercase(mut self, uppercase: bool) -> HexViewBuilder<'a> {
+        self.hex_view.byte_uppercase = uppercase;
+        self
+    }
+
+    /// Follows every row with a line of the bytes as signed decimals, each right aligned under its
+    /// hex byte. The hex columns are widened to four characters to fit `-128`.
+    pub fn stacked_signed_decimal(mut self, stacked: bool) -> HexViewBuilder<'a> {
+        self.hex_view.stacked_signed_decimal = stacked;
+        self
+    }
+
+    /// Writes `prefix`, e.g. `0x`, in front of every byte of the hex panel. The case of the prefix
+    /// is kept as given, independent of [uppercase](#method.uppercase).
+    pub fn hex_prefix<'b: 'a>(mut self, prefix: Option<&'b str>) -> HexViewBuilder<'a> {
+        self.hex_view.hex_prefix = prefix;
+        self
+    }
+
+    /// Sets the divider in front of the char panel, `"| "` by default.
+    ///
+    /// The char panel is closed by the same divider in reverse order, an empty divider leaves
+    /// only the gap between the panels.
+    pub fn panel_divider<'b: 'a>(mut self, divider: &'b str) -> HexViewBuilder<'a> {
+        self.hex_view.panel_divider = divider;
+        self
+    }
+
+    /// The text shown in the hex columns in front of the first byte of an unaligned
+    /// [address_offset](#method.address_offset), two spaces by default.
+    ///
+    /// The fill should be two characters wide to keep the columns aligned.
+    pub fn begin_pad_fill<'b: 'a>(mut self, fill: &'b str) -> HexViewBuilder<'a> {
+        self.hex_view.begin_pad_fill = fill;
+        self
+    }
+
+    /// Pads the char panel of a short row with spaces, so the closing divider lines up with the
+    /// rows above it. Enabled by default, when disabled the divider directly follows the last char.
+    pub fn align_char_panel(mut self, align: bool) -> HexViewBuilder<'a> {
+        self.hex_view.align_char_panel = align;
+        self
+    }
+
+    /// Starts the char panel at `column` (0-based) when the hex section is narrower, so dumps
+    /// with different row widths line up.
+    pub fn char_panel_column(mut self, column: usize) -> HexViewBuilder<'a> {
+        self.hex_view.char_panel_column = Some(column);
+        self
+    }
+
+    /// Wraps the char panel onto a continuation line when a row would be wider than `width`.
+    ///
+    /// The [right to left](#method.rtl) and [od_style](#method.od_style) layouts are never wrapped.
+    pub fn max_width(mut self, width: usize) -> HexViewBuilder<'a> {
+        self.hex_view.max_width = Some(width);
+        self
+    }
+
+    /// Aligns the rows to multiples of the row width (the default).
+    ///
+    /// When disabled, the first row starts at the address offset itself and every address is
+    /// the address of the first byte on its row.
+    pub fn align_addresses(mut self, align: bool) -> HexViewBuilder<'a> {
+        self.hex_view.align_addresses = align;
+        self
+    }
+
+    /// Aligns the first row to a multiple of `alignment` instead of the row width, e.g. to start
+    /// the rows at 8 byte boundaries with a row width of 16. A zero `alignment` uses the row width.
+    ///
+    /// The begin padding is always shorter than a row, so an alignment larger than the row width
+    /// aligns to the row width.
+    pub fn align_to(mut self, alignment: usize) -> HexViewBuilder<'a> {
+        self.hex_view.align_to = alignment;
+        self
+    }
+
+    /// Shows empty data as a single row holding the address of the row the address offset falls
+    /// in and a blank grid (the default), or as no row at all.
+    pub fn empty_shows_address(mut self, show: bool) -> HexViewBuilder<'a> {
+        self.hex_view.empty_shows_address = show;
+        self
+    }
+
+    /// Prints the 1-based row number in front of the address column.
+    pub fn show_row_numbers(mut self, show: bool) -> HexViewBuilder<'a> {
+        self.hex_view.show_row_numbers = show;
+        self
+    }
+
+    /// Prints the 0-based row index in hex in front of the address column, after the
+    /// [row number](#method.show_row_numbers). It is zero padded to at least 4 digits.
+    pub fn show_hex_row_index(mut self, show: bool) -> HexViewBuilder<'a> {
+        self.hex_view.show_hex_row_index = show;
+        self
+    }
+
+    /// Appends the address just past the last byte of every row, after the char panel.
+    pub fn show_end_address(mut self, show: bool) -> HexViewBuilder<'a> {
+        self.hex_view.show_end_address = show;
+        self
+    }
+
+    /// Appends a final line with the address just past the last byte, like `hexdump`, which shows
+    /// the size of the data at a glance. It is omitted when empty data shows no row, see
+    /// [empty_shows_address](#method.empty_shows_address).
+    pub fn show_final_offset(mut self, show: bool) -> HexViewBuilder<'a> {
+        self.hex_view.show_final_offset = show;
+        self
+    }
+
+    /// Appends the position of every row as a percentage, after the char panel.
+    ///
+    /// The position is relative to the shown data, up to the [limit](#method.limit), or to the
+    /// [total_size](#method.total_size) when it is given.
+    pub fn show_percentage(mut self, show: bool) -> HexViewBuilder<'a> {
+        self.hex_view.show_percentage = show;
+        self
+    }
+
+    /// Sets the size of the whole file the data was taken from, the
+    /// [percentages](#method.show_percentage) are then the positions of the row addresses within
+    /// that size.
+    pub fn total_size(mut self, size: usize) -> HexViewBuilder<'a> {
+        self.hex_view.total_size = Some(size);
+        self
+    }
+
+    /// Shows the bytes of a short last row without the padding columns, the char panel directly
+    /// follows the last byte.
+    pub fn compact_last_row(mut self, compact: bool) -> HexViewBuilder<'a> {
+        self.hex_view.compact_last_row = compact;
+        self
+    }
+
+    /// Writes the printable ASCII bytes in the hex panel as quoted chars, e.g. `'A'`, and the other
+    /// bytes as escapes, e.g. `\x00`. The quote and the backslash are escaped too.
+    ///
+    /// The quoted chars are one column narrower than the escapes, they are right aligned to the
+    /// width of an escape. The hex prefix is ignored in this mode.
+    pub fn inline_ascii(mut self, inline_ascii: bool) -> HexViewBuilder<'a> {
+        self.hex_view.inline_ascii = inline_ascii;
+        self
+    }
+
+    /// Adds a second hex panel after the first one with the bytes of every row in reverse order,
+    /// e.g. to spot palindromes. The padding of a short row is mirrored as well.
+    pub fn show_reversed_column(mut self, show: bool) -> HexViewBuilder<'a> {
+        self.hex_view.show_reversed_column = show;
+        self
+    }
+
+    /// Adds a column between the hex and char panels marking every printable ASCII byte with a
+    /// `.` and every other byte with a `#`.
+    pub fn show_class_bar(mut self, show: bool) -> HexViewBuilder<'a> {
+        self.hex_view.show_class_bar = show;
+        self
+    }
+
+    /// Adds a column after the hex panel holding the checksum of the bytes of each row, the
+    /// padding is not included.
+    pub fn show_row_checksum(mut self, kind: ChecksumKind) -> HexViewBuilder<'a> {
+        self.hex_view.row_checksum = Some(kind);
+        self
+    }
+
+    /// Collapses consecutive identical rows into a single `*` line, like `hexdump` does.
+    pub fn squeeze(mut self, squeeze: bool) -> HexViewBuilder<'a> {
+        self.hex_view.squeeze = squeeze;
+        self
+    }
+
+    /// Adds the number of collapsed rows to the [squeeze](#method.squeeze) marker.
+    pub fn squeeze_count(mut self, count: bool) -> HexViewBuilder<'a> {
+        self.hex_view.squeeze_count = count;
+        self
+    }
+
+    /// Mirrors every row: the char panel comes first and the address last, with the bytes in
+    /// reverse order.
+    ///
+    /// The alignment of the header and footer is best-effort in this mode, they are not mirrored.
+    pub fn rtl(mut self, rtl: bool) -> HexViewBuilder<'a> {
+        self.hex_view.rtl = rtl;
+        self
+    }
+
+    /// Separates the address, hex and char sections with a single tab instead of spaces, the
+    /// bytes of the hex panel stay separated by spaces.
+    pub fn tab_separated(mut self, tab_separated: bool) -> HexViewBuilder<'a> {
+        self.hex_view.tab_separated = tab_separated;
+        self
+    }
+
+    /// Turns the hex panel into a table: the bytes are separated by `│` instead of spaces, and a
+    /// border is drawn above the first and below the last row. The group gaps are ignored.
+    pub fn cell_borders(mut self, cell_borders: bool) -> HexViewBuilder<'a> {
+        self.hex_view.cell_borders = cell_borders;
+        self
+    }
+
+    /// Wraps every row of the [HTML export](struct.HexView.html#method.to_html) in a span with
+    /// the id `off-` followed by the address of the row in hex, e.g. `off-0010`, so the rows can
+    /// be linked to.
+    pub fn html_row_anchors(mut self, anchors: bool) -> HexViewBuilder<'a> {
+        self.hex_view.html_row_anchors = anchors;
+        self
+    }
+
+    /// Prints `title`, e.g. the name of the dumped file, on its own line above the dump.
+    pub fn title<'b: 'a>(mut self, title: &'b str) -> HexViewBuilder<'a> {
+        self.hex_view.title = Some(title);
+        self
+    }
+
+    /// Ends every line but the last with a `\`, to embed the dump in a line continued context
+    /// like a shell command.
+    pub fn line_continuation(mut self, line_continuation: bool) -> HexViewBuilder<'a> {
+        self.hex_view.line_continuation = line_continuation;
+        self
+    }
+
+    /// Writes `prefix` at the start of every line, after the [indentation](#method.indent_level).
+    pub fn line_prefix<'b: 'a>(mut self, prefix: &'b str) -> HexViewBuilder<'a> {
+        self.hex_view.line_prefix = prefix;
+        self
+    }
+
+    /// Indents every line by `level` times the [indent_width](#method.indent_width), to nest the
+    /// dumps of a recursive structure.
+    pub fn indent_level(mut self, level: usize) -> HexViewBuilder<'a> {
+        self.hex_view.indent_level = level;
+        self
+    }
+
+    /// Sets the number of spaces per [indent_level](#method.indent_level), 4 by default.
+    pub fn indent_width(mut self, width: usize) -> HexViewBuilder<'a> {
+        self.hex_view.indent_width = width;
+        self
+    }
+
+    /// Lists only the runs of at least `min_len` printable ASCII bytes instead of the dump, like the
+    /// `strings` utility. Every run is printed on its own line, after the address of its first
+    /// byte.
+    pub fn strings_only(mut self, min_len: usize) -> HexViewBuilder<'a> {
+        self.hex_view.strings_min_len = Some(min_len);
+        self
+    }
+
+    /// Prints a header row with the column indices above the dump.
+    pub fn show_header(mut self, show: bool) -> HexViewBuilder<'a> {
+        self.hex_view.show_header = show;
+        self
+    }
+
+    /// Marks every `tick`-th column index in the header, a tick of 0 disables the marks.
+    pub fn header_tick(mut self, tick: usize) -> HexViewBuilder<'a> {
+        self.hex_view.header_tick = tick;
+        self
+    }
+
+    /// Prints the bytes in `range` (relative to the start of the data) bit by bit below the dump.
+    pub fn bitfield(mut self, range: Range<usize>) -> HexViewBuilder<'a> {
+        self.hex_view.bitfield = Some(range);
+        self
+    }
+
+    /// Leaves the char panel of a row blank when none of its bytes has a printable glyph, instead
+    /// of a run of placeholders. The following columns stay aligned.
+    pub fn hide_empty_char_panel(mut self, hide: bool) -> HexViewBuilder<'a> {
+        self.hex_view.hide_empty_char_panel = hide;
+        self
+    }
+
+    /// Lists the non-ASCII glyphs of the char panel in a footer below the dump, together with
+    /// their byte values, to help reading an unfamiliar codepage.
+    ///
+    /// Only the glyphs of the bytes in the dump are listed, ordered by byte value.
+    pub fn show_codepage_legend(mut self, show: bool) -> HexViewBuilder<'a> {
+        self.hex_view.show_codepage_legend = show;
+        self
+    }
+
+    /// Tags the byte at `offset` (relative to the start of the data) with `label`.
+    ///
+    /// The tags are listed in a footer below the dump, ordered by offset.
+    pub fn tag(mut self, offset: usize, label: char) -> HexViewBuilder<'a> {
+        self.hex_view.tags.push((offset, label));
+        self.hex_view.tags.sort_by_key(|&(offset, _)| offset);
+        self
+    }
+
+    /// Focuses on the bytes in `ranges` (relative to the start of the data), the other bytes are
+    /// dimmed unless they are [highlighted](#method.highlight). When
+    /// [use_color](#method.use_color) is disabled, the hex panel is left alone and the other
+    /// bytes are replaced by dots in the char panel.
+    pub fn focus(mut self, ranges: &[Range<usize>]) -> HexViewBuilder<'a> {
+        self.hex_view.focus.extend_from_slice(ranges);
+        self
+    }
+
+    /// Marks the bytes in `range` (relative to the start of the data) with a line of carets
+    /// below their row, which works without colors.
+    pub fn underline(mut self, range: Range<usize>) -> HexViewBuilder<'a> {
+        self.hex_view.underlines.push(range);
+        self
+    }
+
+    /// Marks the bytes that differ from the byte in the same column of the previous row.
+    ///
+    /// The changed bytes are colored red with [use_color](#method.use_color), otherwise they are
+    /// marked with carets below their row. The first row has nothing to compare against.
+    pub fn diff_against_previous(mut self, diff: bool) -> HexViewBuilder<'a> {
+        self.hex_view.diff_against_previous = diff;
+        self
+    }
+
+    /// Enables the ANSI color escape codes in the output.
+    pub fn use_color(mut self, use_color: bool) -> HexViewBuilder<'a> {
+        self.hex_view.use_color = use_color;
+        self
+    }
+
+    /// Colors the bytes in `range`, given as offsets relative to the start of the data.
+    ///
+    /// Any kind of range can be used, e.g. `4..7` or `4..=6`. When highlights overlap, the one
+    /// added last wins. Highlights take precedence over the [color_fn](#method.color_fn).
+    pub fn highlight<R: RangeBounds<usize>>(self, range: R, color: Color) -> HexViewBuilder<'a> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => usize::MAX,
+        };
+
+        self.highlight_styled(Highlight { range: start..end, fg: Some(color), bg: None })
+    }
+
+    /// Colors the foreground and/or background of the bytes in the range of `highlight`, like
+    /// [highlight](#method.highlight).
+    pub fn highlight_styled(mut self, highlight: Highlight) -> HexViewBuilder<'a> {
+        self.hex_view.highlights.push(highlight);
+        self
+    }
+
+    /// Colors the `len` bytes starting at offset `start`.
+    pub fn highlight_len(self, start: usize, len: usize, color: Color) -> HexViewBuilder<'a> {
+        self.highlight(start..start.saturating_add(len), color)
+    }
+
+    /// Adds a column after the char panel with the texts of `decode_fn`, e.g. the mnemonics of a
+    /// trivial disassembler.
+    ///
+    /// `decode_fn` is given the remaining bytes of a row and returns the number of bytes it
+    /// consumed and the text for them, or `None` to stop decoding the row. The texts of a row are
+    /// separated by `; `.
+    pub fn decode_fn(mut self, decode_fn: fn(&[u8]) -> Option<(usize, String)>) -> HexViewBuilder<'a> {
+        self.hex_view.decode_fn = Some(decode_fn);
+        self
+    }
+
+    /// Colors the bytes for which `color_fn` returns a color, given their offset and value.
+    ///
+    /// The colors are only emitted when [use_color](#method.use_color) is enabled.
+    pub fn color_fn(mut self, color_fn: fn(usize, u8) -> Option<Color>) -> HexViewBuilder<'a> {
+        self.hex_view.color_fn = Some(color_fn);
+        self
+    }
+
+    /// Colors the two hex digits of every byte separately with the colors `nibble_color_fn`
+    /// returns for the high and low nibble, given the offset and value of the byte.
+    ///
+    /// The nibble colors replace the other colors of the hex panel and are only emitted when
+    /// [use_color](#method.use_color) is enabled.
+    pub fn nibble_color_fn(mut self, nibble_color_fn: NibbleColorFn) -> HexViewBuilder<'a> {
+        self.hex_view.nibble_color_fn = Some(nibble_color_fn);
+        self
+    }
+
+    /// Configures the view to match the canonical output of `hexdump -C`.
+    ///
+    /// That is 16 lowercase bytes per row in two groups of 8, the printable ASCII characters
+    /// between bars, squeezed duplicate rows and a final line holding the end offset.
+    pub fn hexdump_compat(self) -> HexViewBuilder<'a> {
+        self.show_final_offset(true)
+            .empty_shows_address(false)
+            .row_width(16)
+            .align_char_panel(false)
+            .group_size(8)
+            .group_gap(2)
+            .uppercase(false)
+            .address_radix(Radix::Hexadecimal)
+            .codepage(byte_mapping::CODEPAGE_ASCII)
+            .panel_divider("|")
+            .squeeze(true)
+    }
+
+    /// Configures the view to resemble the hex pane of network capture tools.
+    ///
+    /// That is a colon after the address, 16 lowercase bytes per row in four groups of 4 and the
+    /// printable ASCII characters without a divider or trailing padding.
+    pub fn pcap_style(self) -> HexViewBuilder<'a> {
+        self.row_width(16)
+            .address_suffix(": ")
+            .group_size(4)
+            .group_gap(2)
+            .uppercase(false)
+            .address_radix(Radix::Hexadecimal)
+            .codepage(byte_mapping::CODEPAGE_ASCII)
+            .panel_divider("")
+            .align_char_panel(false)
+    }
+
+    /// Configures the view to match the output of `od -An -tx1`: 16 lowercase bytes per row, each
+    /// preceded by a space, without the address and the char panel. Identical rows are squeezed
+    /// into a `*` line.
+    pub fn od_style(mut self) -> HexViewBuilder<'a> {
+        self.hex_view.od_layout = true;
+        self.row_width(16)
+            .align_addresses(false)
+            .byte_uppercase(false)
+            .empty_shows_address(false)
+            .squeeze(true)
+    }
+
+    /// Groups the bytes into records of `size` bytes, separated by the group gap.
+    ///
+    /// This is the same as [group_size](#method.group_size), so it takes precedence over the
+    /// grouping of the [word_size](#method.word_size) in either call order.
+    pub fn element_size(self, size: usize) -> HexViewBuilder<'a> {
+        self.group_size(size)
+    }
+
+    /// Wraps the rows after `count` records of [element_size](#method.element_size) bytes,
+    /// instead of after [row_width](#method.row_width) bytes.
+    pub fn elements_per_row(mut self, count: usize) -> HexViewBuilder<'a> {
+        self.hex_view.elements_per_row = Some(count);
+        self
+    }
+
+    pub fn finish(mut self) -> HexView<'a> {
+        if let Some(count) = self.hex_view.elements_per_row {
+            self.hex_view.row_width = count * std::cmp::max(1, self.hex_view.group_len());
+        }
+
+        self.hex_view
+    }
+}
+
+/// The measures of a view that depend on all of its data, computed once per render instead of
+/// once per row.
+#[derive(Clone, Copy)]
+struct Layout {
+    row_count: usize,
+    char_column_width: usize,
+}
+
+#[derive(Default)]
+struct Padding {
+    left: usize,
+    right: usize,
+}
+
+impl Padding {
+    fn new(left_padding: usize, right_padding: usize) -> Padding {
+        Padding {
+            left: left_padding,
+            right: right_padding,
+        }
+    }
+}
+
+/// Displays a value as zero padded hexadecimal digits.
+struct Hex {
+    value: usize,
+    width: usize,
+    uppercase: bool,
+}
+
+impl Hex {
+    fn new(value: usize, width: usize, uppercase: bool) -> Hex {
+        Hex {
+            value,
+            width,
+            uppercase,
+        }
+    }
+}
+
+impl std::fmt::Display for Hex {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        if self.uppercase {
+            write!(f, "{:0width$X}", self.value, width = self.width)
+        } else {
+            write!(f, "{:0width$x}", self.value, width = self.width)
+        }
+    }
+}
+
+fn fmt_class_bar<I: Iterator<Item = Cell>>(f: &mut Formatter, cells: I) -> Result {
+    for cell in cells {
+        match cell {
+            Some((_, byte)) if byte_mapping::is_printable_ascii(byte) => write!(f, ".")?,
+            Some(_) => write!(f, "#")?,
+            None => write!(f, " ")?,
+        }
+    }
+
+    Ok(())
+}
+
+fn fmt_tags(f: &mut Formatter, tags: &[(usize, char)]) -> Result {
+    let mut separator = "";
+
+    for &(offset, label) in tags.iter() {
+        write!(f, "{}{}=offset {}", separator, label, offset)?;
+        separator = ", ";
+    }
+
+    Ok(())
+}
+
+/// A single display row of a [HexView](struct.HexView.html).
+struct Row<'b> {
+    index: usize,
+    address: usize,
+    offset: usize,
+    bytes: Cow<'b, [u8]>,
+    stride: usize,
+    padding: Padding,
+}
+
+impl<'b> Row<'b> {
+    /// Returns the columns of the row, the padding columns are `None`.
+    fn cells(&self) -> impl DoubleEndedIterator<Item = Cell> + '_ {
+        self.padded_cells(self.padding.right)
+    }
+
+    /// Returns the columns of the row with `right_padding` instead of the padding of the row.
+    fn padded_cells(&self, right_padding: usize) -> impl DoubleEndedIterator<Item = Cell> + '_ {
+        let offset = self.offset;
+
+        (0..self.padding.left).map(|_| None)
+            .chain(self.bytes.iter().enumerate().step_by(self.stride).map(move |(index, &byte)| {
+                Some((offset + index, byte))
+            }))
+            .chain((0..right_padding).map(|_| None))
+    }
+
+    /// Returns the bytes shown in the row.
+    fn values(&self) -> impl Iterator<Item = u8> + '_ {
+        self.bytes.iter().step_by(self.stride).cloned()
+    }
+
+    /// Returns the number of bytes shown in the row.
+    fn len(&self) -> usize {
+        self.bytes.len().div_ceil(self.stride)
+    }
+
+    /// Returns the address just past the last byte of the row.
+    fn end_address(&self) -> usize {
+        self.address + self.padding.left + self.bytes.len()
+    }
+}
+
+/// A column of a row, holding the offset and value of a byte or nothing for padding.
+type Cell = Option<(usize, u8)>;
+
+/// Iterates over the display rows of the data.
+struct Rows<'b> {
+    data: Fragments<'b>,
+    row_width: usize,
+    width_changes: Vec<(usize, usize)>,
+    columns: usize,
+    stride: usize,
+    align_addresses: bool,
+    index: usize,
+    address: usize,
+    offset: usize,
+    left_padding: usize,
+    first: bool,
+}
+
+impl<'b> Iterator for Rows<'b> {
+    type Item = Row<'b>;
+
+    fn next(&mut self) -> Option<Row<'b>> {
+        if !self.first && self.offset >= self.data.len() {
+            return None;
+        }
+
+        let (row_width, section_end) = self.section(self.offset);
+        let left = self.left_padding;
+        let row_width = std::cmp::max(row_width, left + 1);
+        let remaining = std::cmp::min(self.data.len(), section_end) - self.offset;
+        let count = std::cmp::min(row_width - left, remaining.div_ceil(self.stride));
+        let span = std::cmp::min(count * self.stride, remaining);
+        let right = if self.data.is_empty() {
+            calculate_end_padding(left, self.columns)
+        } else {
+            self.columns - left - count
+        };
+
+        let row = Row {
+            index: self.index,
+            address: self.address,
+            offset: self.offset,
+            bytes: self.data.slice(self.offset..self.offset + span),
+            stride: self.stride,
+            padding: Padding::new(left, right),
+        };
+
+        self.address += if self.align_addresses { row_width * self.stride } else { span };
+        self.offset += span;
+        self.left_padding = 0;
+        self.index += 1;
+        self.first = false;
+
+        Some(row)
+    }
+}
+
+/// A line of the rows, a row or the marker of the repeated rows that were squeezed.
+enum RowLine<'b> {
+    Row(Row<'b>),
+    Squeezed(usize),
+}
+
+/// Iterates over the rows, with the full rows that repeat the previous one
+/// [squeezed](struct.HexViewBuilder.html#method.squeeze) into a marker.
+struct SqueezedRows<'b> {
+    rows: std::iter::Take<Rows<'b>>,
+    squeeze: bool,
+    previous: Option<Cow<'b, [u8]>>,
+    pending: Option<Row<'b>>,
+}
+
+impl<'b> Iterator for SqueezedRows<'b> {
+    type Item = RowLine<'b>;
+
+    fn next(&mut self) -> Option<RowLine<'b>> {
+        if let Some(row) = self.pending.take() {
+            return Some(self.shown(row));
+        }
+
+        let mut squeezed = 0;
+
+        for row in self.rows.by_ref() {
+            let is_full = row.padding.left == 0 && row.padding.right == 0;
+
+            if self.squeeze && is_full && self.previous.as_deref() == Some(&row.bytes[..]) {
+                squeezed += 1;
+            } else if squeezed != 0 {
+                self.pending = Some(row);
+                return Some(RowLine::Squeezed(squeezed));
+            } else {
+                return Some(self.shown(row));
+            }
+        }
+
+        if squeezed != 0 { Some(RowLine::Squeezed(squeezed)) } else { None }
+    }
+}
+
+impl<'b> SqueezedRows<'b> {
+    fn shown(&mut self, row: Row<'b>) -> RowLine<'b> {
+        let is_full = row.padding.left == 0 && row.padding.right == 0;
+        self.previous = if is_full { Some(row.bytes.clone()) } else { None };
+        RowLine::Row(row)
+    }
+}
+
+impl<'b> Rows<'b> {
+    /// Returns the row width at `offset` and the offset at which the width changes next.
+    fn section(&self, offset: usize) -> (usize, usize) {
+        let row_width = self.width_changes.iter()
+            .take_while(|&&(at_offset, _)| at_offset <= offset)
+            .last()
+            .map_or(self.row_width, |&(_, width)| width);
+        let section_end = self.width_changes.iter()
+            .map(|&(at_offset, _)| at_offset)
+            .find(|&at_offset| at_offset > offset)
+            .unwrap_or(usize::MAX);
+
+        (row_width, section_end)
+    }
+}
+
+/// Counts the bytes written to it.
+struct LengthCounter(usize);
+
+impl std::fmt::Write for LengthCounter {
+    fn write_str(&mut self, s: &str) -> Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+/// Passes the lines written to it to a callback, the last line is kept in `line`.
+struct LineSplitter<F: FnMut(&str)> {
+    line: String,
+    callback: F,
+}
+
+impl<F: FnMut(&str)> std::fmt::Write for LineSplitter<F> {
+    fn write_str(&mut self, s: &str) -> Result {
+        let mut parts = s.split('\n');
+        self.line.push_str(parts.next().unwrap_or(""));
+
+        for part in parts {
+            (self.callback)(&self.line);
+            self.line.clear();
+            self.line.push_str(part);
+        }
+
+        Ok(())
+    }
+}
+
+/// Starts every non-empty line written to it with a prefix, and ends every line but the last with
+/// a backslash when `continuation` is set.
+struct DecoratedLines<W: Write> {
+    inner: W,
+    prefix: String,
+    continuation: bool,
+    line_start: bool,
+}
+
+impl<W: Write> std::fmt::Write for DecoratedLines<W> {
+    fn write_str(&mut self, s: &str) -> Result {
+        for (index, part) in s.split('\n').enumerate() {
+            if index != 0 {
+                self.inner.write_str(if self.continuation { "\\\n" } else { "\n" })?;
+                self.line_start = true;
+            }
+
+            if !part.is_empty() {
+                if self.line_start {
+                    self.inner.write_str(&self.prefix)?;
+                    self.line_start = false;
+                }
+
+                self.inner.write_str(part)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Displays the lines of a [HexView](struct.HexView.html), without their decorations.
+struct LinesDisplay<'v, 'a: 'v> {
+    view: &'v HexView<'a>,
+}
+
+impl<'v, 'a> std::fmt::Display for LinesDisplay<'v, 'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        self.view.fmt_lines(f)
+    }
+}
+
+/// Displays a single row of a [HexView](struct.HexView.html).
+struct RowDisplay<'v, 'a: 'v, 'b> {
+    view: &'v HexView<'a>,
+    row: Row<'b>,
+}
+
+impl<'v, 'a, 'b> std::fmt::Display for RowDisplay<'v, 'a, 'b> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        self.view.fmt_line(f, &self.row)
+    }
+}
+
+/// Formats `value` in decimal with `separator` between every three digits.
+fn group_thousands(value: usize, separator: char) -> String {
+    let digits = value.to_string();
+    let mut result = String::new();
+
+    for (index, digit) in digits.chars().enumerate() {
+        if index != 0 && (digits.len() - index).is_multiple_of(3) {
+            result.push(separator);
+        }
+
+        result.push(digit);
+    }
+
+    result
+}
+
+/// Formats `value` with a binary prefix and one decimal, e.g. `2.5M`, values below 1024 are
+/// formatted as they are.
+fn human_size(value: usize) -> String {
+    const PREFIXES: [char; 6] = ['K', 'M', 'G', 'T', 'P', 'E'];
+
+    if value < 1024 {
+        return value.to_string();
+    }
+
+    let mut size = value as f64 / 1024.0;
+    let mut prefix = 0;
+
+    while size >= 1024.0 && prefix + 1 < PREFIXES.len() {
+        size /= 1024.0;
+        prefix += 1;
+    }
+
+    format!("{:.1}{}", size, PREFIXES[prefix])
+}
+
+/// Formats a printable ASCII byte as a quoted char and any other byte as a `\x` escape.
+fn inline_ascii_entry(byte: u8, uppercase: bool) -> String {
+    if byte_mapping::is_printable_ascii(byte) && byte != b'\'' && byte != b'\\' {
+        format!("'{}'", byte as char)
+    } else {
+        format!("\\x{}", Hex::new(byte as usize, 2, uppercase))
+    }
+}
+
+fn calculate_begin_padding(address_offset: usize, row_width: usize) -> usize {
+    debug_assert!(row_width != 0, "A zero row width is can not be used to calculate the begin padding");
+    address_offset % row_width
+}
+
+fn calculate_end_padding(data_size: usize, row_width: usize) -> usize {
+    debug_assert!(row_width != 0, "A zero row width is can not be used to calculate the end padding");
+    (row_width - data_size % row_width) % row_width
+}
+
+impl<'a> HexView<'a> {
+    fn shown_data(&self) -> Fragments<'a> {
+        match self.limit {
+            Some(limit) if limit < self.data.len() => self.data.prefix(limit),
+            _ => self.data.clone(),
+        }
+    }
+
+    fn shown_len(&self) -> usize {
+        match self.limit {
+            Some(limit) => std::cmp::min(limit, self.data.len()),
+            None => self.data.len(),
+        }
+    }
+
+    /// Returns the address of the first byte as it is printed.
+    fn shown_address_offset(&self) -> usize {
+        if self.relative_addresses {
+            0
+        } else {
+            self.address_offset
+        }
+    }
+
+    fn begin_padding(&self) -> usize {
+        self.begin_padding_at(self.shown_address_offset())
+    }
+
+    /// Returns the number of padding columns in front of a first byte shown at `address`.
+    fn begin_padding_at(&self, address: usize) -> usize {
+        if self.align_addresses && self.stride == 1 {
+            let alignment = if self.align_to == 0 { self.row_width } else { self.align_to };
+            calculate_begin_padding(address, alignment) % self.row_width
+        } else {
+            0
+        }
+    }
+
+    fn rows(&self) -> Rows<'a> {
+        let begin_padding = self.begin_padding();
+
+        Rows {
+            data: self.shown_data(),
+            row_width: self.row_width,
+            width_changes: self.width_changes.clone(),
+            columns: self.columns(),
+            stride: self.stride,
+            align_addresses: self.align_addresses,
+            index: 0,
+            address: self.shown_address_offset() - begin_padding,
+            offset: 0,
+            left_padding: begin_padding,
+            first: true,
+        }
+    }
+
+    /// Returns the lines of the rows, with the repeated rows squeezed.
+    fn row_lines(&self) -> SqueezedRows<'a> {
+        let count = if self.data.is_empty() && !self.empty_shows_address { 0 } else { usize::MAX };
+
+        SqueezedRows {
+            rows: self.rows().take(count),
+            squeeze: self.squeeze,
+            previous: None,
+            pending: None,
+        }
+    }
+
+    /// Returns the number of columns of the panels, which is the width of the widest row.
+    fn columns(&self) -> usize {
+        self.width_changes.iter().fold(self.row_width, |columns, &(_, width)| std::cmp::max(columns, width))
+    }
+
+    /// Returns the data between the gaps as views of their own, at the addresses after the gaps.
+    fn gap_regions(&self) -> Vec<HexView<'a>> {
+        let mut regions: Vec<HexView<'a>> = Vec::new();
+        let mut start = 0;
+        let mut address = self.address_offset;
+
+        for &(offset, len) in self.gaps.iter().chain(std::iter::once(&(self.data.len(), 0))) {
+            if offset > start || (regions.is_empty() && offset == self.data.len()) {
+                let mut region = self.clone();
+                region.gaps = Vec::new();
+                region.data = self.data.range(start..offset);
+                region.address_offset = address;
+
+                if !regions.is_empty() {
+                    region.title = None;
+                    region.show_header = false;
+                }
+
+                regions.push(region);
+            }
+
+            address += offset - start + len;
+            start = offset;
+        }
+
+        regions
+    }
+
+    /// Returns a copy of the view with its [Layout](struct.Layout.html) computed, to be rendered.
+    fn laid_out(&self) -> HexView<'a> {
+        let mut view = self.clone();
+        view.layout = Some(Layout {
+            row_count: self.count_rows(),
+            char_column_width: self.measure_char_column(),
+        });
+        view
+    }
+
+    fn row_count(&self) -> usize {
+        self.layout.map_or_else(|| self.count_rows(), |layout| layout.row_count)
+    }
+
+    fn count_rows(&self) -> usize {
+        if !self.width_changes.is_empty() {
+            return self.rows().count();
+        }
+
+        std::cmp::max(1, (self.begin_padding() + self.shown_len().div_ceil(self.stride)).div_ceil(self.row_width))
+    }
+
+    /// Returns the width of the row number and hex row index columns, including their gaps.
+    fn row_number_width(&self) -> usize {
+        self.decimal_row_number_width() + self.hex_row_index_width()
+    }
+
+    fn decimal_row_number_width(&self) -> usize {
+        if self.show_row_numbers {
+            self.row_count().to_string().len() + 2
+        } else {
+            0
+        }
+    }
+
+    fn hex_row_index_width(&self) -> usize {
+        if self.show_hex_row_index {
+            std::cmp::max(4, format!("{:x}", self.row_count() - 1).len()) + 2
+        } else {
+            0
+        }
+    }
+
+    /// Returns the width of the address column, which is wide enough for the largest address.
+    fn address_width(&self) -> usize {
+        let last_address = self.shown_address_offset() + self.data.len();
+        if self.human_address {
+            return std::cmp::max(ADDRESS_WIDTH, self.min_address_width);
+        }
+
+        if let Some(shift) = self.segment_shift {
+            let segment = (last_address & !0xFFFF) >> shift;
+            return std::cmp::max(self.min_address_width, std::cmp::max(4, format!("{:x}", segment).len()) + 5);
+        }
+
+        let width = match (self.address_radix, self.address_grouping) {
+            (Radix::Binary, _) => format!("{:b}", last_address).len(),
+            (Radix::Octal, _) => format!("{:o}", last_address).len(),
+            (Radix::Decimal, Some(separator)) => group_thousands(last_address, separator).chars().count(),
+            (Radix::Decimal, None) => last_address.to_string().len(),
+            (Radix::Hexadecimal, _) => format!("{:x}", last_address).len(),
+        };
+
+        std::cmp::max(std::cmp::max(ADDRESS_WIDTH, self.min_address_width), width)
+    }
+
+    fn address_column_width(&self) -> usize {
+        self.row_number_width() + self.address_width()
+    }
+
+    fn hex_panel_column(&self) -> usize {
+        if self.address_position == Position::Right {
+            return self.row_number_width();
+        }
+
+        self.address_column_width() + self.address_suffix.chars().count()
+    }
+
+    fn hex_panel_width(&self) -> usize {
+        (0..self.columns()).map(|column| self.column_separator_width(column) + self.hex_column_width()).sum()
+    }
+
+    /// Returns the width of a byte in the hex panel, including the hex prefix.
+    fn hex_column_width(&self) -> usize {
+        let width = 2 + self.hex_prefix.map_or(0, |prefix| prefix.chars().count());
+
+        if self.inline_ascii || self.stacked_signed_decimal {
+            std::cmp::max(4, width)
+        } else {
+            width
+        }
+    }
+
+    /// Returns the column the char panel starts at without the
+    /// [char_panel_column](struct.HexViewBuilder.html#method.char_panel_column) setting.
+    fn natural_char_panel_column(&self) -> usize {
+        let class_bar_width = if self.show_class_bar { self.columns() + 2 } else { 0 };
+        let checksum_width = if self.row_checksum.is_some() { 4 } else { 0 };
+        let reversed_column_width = if self.show_reversed_column { self.hex_panel_width() + 2 } else { 0 };
+
+        self.hex_panel_column() + self.hex_panel_width() + reversed_column_width + checksum_width + class_bar_width + 2
+    }
+
+    /// Returns the number of spaces added in front of the char panel to move it to its column.
+    fn char_panel_padding(&self) -> usize {
+        self.char_panel_column.map_or(0, |column| column.saturating_sub(self.natural_char_panel_column()))
+    }
+
+    /// Returns whether the byte has no printable glyph in the codepage.
+    fn is_non_printable(&self, byte: u8) -> bool {
+        let glyph = byte_mapping::as_char(byte, self.codepage);
+        glyph == byte_mapping::NIL || (glyph == '.' && byte != b'.') || !byte_mapping::is_single_width(glyph)
+    }
+
+    /// Returns whether the char panel of the row is left blank.
+    fn hides_char_panel(&self, row: &Row) -> bool {
+        self.hide_empty_char_panel && row.values().all(|byte| self.is_non_printable(byte))
+    }
+
+    /// Returns the width of the char panel, including the dividers.
+    fn char_panel_width(&self) -> usize {
+        self.char_panel_columns() * self.char_column_width() + 2 * self.panel_divider.chars().count()
+    }
+
+    /// Returns the number of bytes shown on a line of the char panel.
+    fn char_panel_columns(&self) -> usize {
+        let columns = self.columns();
+
+        match self.char_row_width {
+            Some(width) if columns != 0 => std::cmp::max(columns, width / columns * columns),
+            _ => columns,
+        }
+    }
+
+    /// Returns the number of columns a byte takes in the char panel.
+    fn char_column_width(&self) -> usize {
+        self.layout.map_or_else(|| self.measure_char_column(), |layout| layout.char_column_width)
+    }
+
+    fn measure_char_column(&self) -> usize {
+        if !self.caret_notation {
+            1
+        } else if self.shown_data().iter().any(|byte| byte >= 0x80) {
+            4
+        } else {
+            2
+        }
+    }
+
+    fn line_width(&self) -> usize {
+        let trailing_address_width = match self.address_position {
+            Position::Left => 0,
+            Position::Right => 2 + self.address_width(),
+        };
+
+        self.natural_char_panel_column() + self.char_panel_padding() + self.char_panel_width() + trailing_address_width
+    }
+
+    /// Returns the foreground and background color of a byte.
+    fn byte_style(&self, offset: usize, byte: u8) -> (Option<Color>, Option<Color>) {
+        if !self.use_color {
+            return (None, None);
+        }
+
+        let highlight = self.highlights.iter()
+            .rev()
+            .find(|highlight| highlight.range.contains(&offset));
+
+        match highlight {
+            Some(highlight) => (highlight.fg, highlight.bg),
+            None if self.differs_from_previous_row(offset) => (Some(Color::Red), None),
+            None => (self.color_fn.and_then(|color_fn| color_fn(offset, byte)), None),
+        }
+    }
+
+    fn fmt_byte_styled<T: std::fmt::Display>(&self, f: &mut Formatter, offset: usize, byte: u8, value: T) -> Result {
+        let is_highlighted = self.highlights.iter().any(|highlight| highlight.range.contains(&offset));
+
+        if self.use_color && !self.is_focused(offset) && !is_highlighted {
+            return color::fmt_dimmed(f, value);
+        }
+
+        let (fg, bg) = self.byte_style(offset, byte);
+        color::fmt_styled(f, fg, bg, value)
+    }
+
+    /// Returns the number of bytes of the groups of the hex panel, the group size or else the word
+    /// size, 0 without groups.
+    fn group_len(&self) -> usize {
+        match (self.group_size, self.word_size) {
+            (0, word_size) if word_size > 1 => word_size,
+            (group_size, _) => group_size,
+        }
+    }
+
+    /// Returns whether the byte at `offset` is in the focus, which holds all bytes without one.
+    fn is_focused(&self, offset: usize) -> bool {
+        self.focus.is_empty() || self.focus.iter().any(|range| range.contains(&offset))
+    }
+
+    /// Returns whether the byte at `offset` differs from the one a row above it.
+    fn differs_from_previous_row(&self, offset: usize) -> bool {
+        let row_span = self.row_width * self.stride;
+
+        self.diff_against_previous && offset >= row_span && self.data.get(offset) != self.data.get(offset - row_span)
+    }
+
+    fn column_separator_width(&self, column: usize) -> usize {
+        if column == 0 {
+            0
+        } else if self.cell_borders {
+            3
+        } else if self.group_len() != 0 && column.is_multiple_of(self.group_len()) {
+            self.group_gap
+        } else {
+            1
+        }
+    }
+
+    fn fmt_column_separator(&self, f: &mut Formatter, column: usize) -> Result {
+        if self.cell_borders && column != 0 {
+            return write!(f, " │ ");
+        }
+
+        write!(f, "{:width$}", "", width = self.column_separator_width(column))
+    }
+
+    /// Writes a horizontal border of the hex panel, with `junction` under every column separator.
+    fn fmt_cell_border(&self, f: &mut Formatter, junction: char) -> Result {
+        write!(f, "{:width$}", "", width = self.hex_panel_column())?;
+
+        for column in 0..self.columns() {
+            if column != 0 {
+                write!(f, "─{}─", junction)?;
+            }
+
+            write!(f, "{}", "─".repeat(self.hex_column_width()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the cells as hex, the `begin_padding` columns are written with the begin pad fill.
+    fn fmt_bytes_as_hex<I>(&self, f: &mut Formatter, cells: I, begin_padding: Range<usize>) -> Result
+        where I: Iterator<Item = Cell>
+    {
+        for (column, cell) in cells.enumerate() {
+            self.fmt_column_separator(f, column)?;
+
+            match cell {
+                Some((offset, byte)) => self.fmt_byte_as_hex(f, offset, byte)?,
+                None if begin_padding.contains(&column) => {
+                    write!(f, "{:>width$}", self.begin_pad_fill, width = self.hex_column_width())?
+                }
+                None => write!(f, "{:width$}", "", width = self.hex_column_width())?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Writes the byte as hex, or as an inline ASCII entry, right aligned to the hex column width.
+    fn fmt_byte_as_hex(&self, f: &mut Formatter, offset: usize, byte: u8) -> Result {
+        if self.inline_ascii {
+            let entry = inline_ascii_entry(byte, self.byte_uppercase);
+            write!(f, "{:width$}", "", width = self.hex_column_width() - entry.len())?;
+            return self.fmt_byte_styled(f, offset, byte, entry);
+        }
+
+        let prefix = self.hex_prefix.unwrap_or("");
+        write!(f, "{:>width$}", prefix, width = self.hex_column_width() - 2)?;
+
+        let hex = Hex::new(byte as usize, 2, self.byte_uppercase);
+
+        match self.nibble_color_fn {
+            Some(nibble_color_fn) if self.use_color => {
+                let (high, low) = nibble_color_fn(offset, byte);
+                let digits = hex.to_string();
+                color::fmt_colored(f, high, &digits[..1])?;
+                color::fmt_colored(f, low, &digits[1..])
+            }
+            _ => self.fmt_byte_styled(f, offset, byte, hex),
+        }
+    }
+
+    fn fmt_bytes_as_char<I: Iterator<Item = Cell>>(&self, f: &mut Formatter, cells: I) -> Result {
+        let width = self.char_column_width();
+
+        for cell in cells {
+            match cell {
+                Some((offset, _)) if !self.use_color && !self.is_focused(offset) => {
+                    write!(f, "{}", ".".repeat(width))?
+                }
+                Some((offset, byte)) if self.caret_notation => {
+                    let glyph = format!("{:width$}", byte_mapping::caret_notation(byte), width = width);
+                    self.fmt_byte_styled(f, offset, byte, glyph)?
+                }
+                Some((offset, byte)) => {
+                    let glyph = match self.utf16_char_panel {
+                        Some(endian) => self.utf16_glyph(offset, endian),
+                        None => byte_mapping::as_char(byte, self.codepage),
+                    };
+                    let glyph = if self.sanitize_char_panel && !byte_mapping::is_single_width(glyph) {
+                        self.non_printable_char
+                    } else {
+                        glyph
+                    };
+                    self.fmt_byte_styled(f, offset, byte, glyph)?
+                }
+                None => write!(f, "{:width$}", self.padding_marker, width = width)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the character of the UTF-16 code unit starting at `offset`, or a blank for the
+    /// second byte of a unit.
+    fn utf16_glyph(&self, offset: usize, endian: Endian) -> char {
+        if offset % 2 == 1 {
+            return ' ';
+        }
+
+        let (first, second) = match (self.data.get(offset), self.data.get(offset + 1)) {
+            (Some(first), Some(second)) if offset + 1 < self.shown_len() => (first as u16, second as u16),
+            _ => return std::char::REPLACEMENT_CHARACTER,
+        };
+        let unit = match endian {
+            Endian::Little => second << 8 | first,
+            Endian::Big => first << 8 | second,
+        };
+
+        match std::char::from_u32(unit as u32) {
+            Some(c) if c.is_control() => '.',
+            Some(c) => c,
+            None => std::char::REPLACEMENT_CHARACTER,
+        }
+    }
+
+    /// Returns the cells in the byte order of the words.
+    fn word_ordered<I: Iterator<Item = Cell>>(&self, cells: I) -> std::vec::IntoIter<Cell> {
+        let mut cells: Vec<Cell> = cells.collect();
+
+        if self.word_size > 1 && self.word_endian == Endian::Little {
+            for word in cells.chunks_mut(self.word_size) {
+                word.reverse();
+            }
+        }
+
+        cells.into_iter()
+    }
+
+    /// Returns the gap between the sections of a line.
+    fn section_gap(&self) -> &str {
+        if self.tab_separated { "\t" } else { "  " }
+    }
+
+    fn fmt_line(&self, f: &mut Formatter, row: &Row) -> Result {
+        if self.rtl {
+            return self.fmt_line_rtl(f, row);
+        }
+
+        if self.od_layout {
+            return self.fmt_line_od(f, row);
+        }
+
+        if self.max_width.is_some_and(|max_width| self.line_width() > max_width) {
+            self.fmt_line_start(f, row, true)?;
+            write!(f, "\n{:width$}", "", width = self.hex_panel_column())?;
+        } else {
+            self.fmt_line_start(f, row, false)?;
+            write!(f, "{}{:width$}", self.section_gap(), "", width = self.char_panel_padding())?;
+        }
+
+        let right_padding = if self.compact_last_row { 0 } else { row.padding.right };
+
+        if self.hides_char_panel(row) {
+            write!(f, "{:width$}", "", width = self.char_panel_width())?;
+        } else {
+            self.fmt_char_panel_start(f)?;
+
+            if self.char_panel_columns() > self.columns() {
+                self.fmt_wide_char_row(f, row)?;
+            } else if self.mirror_char_panel {
+                self.fmt_bytes_as_char(f, self.word_ordered(row.padded_cells(right_padding)))?;
+            } else if self.align_char_panel {
+                self.fmt_bytes_as_char(f, row.padded_cells(right_padding))?;
+            } else {
+                self.fmt_bytes_as_char(f, row.padded_cells(0))?;
+            }
+
+            self.fmt_char_panel_end(f)?;
+        }
+
+        self.fmt_decoded(f, row)?;
+        self.fmt_percentage(f, row)?;
+
+        if self.address_position == Position::Right {
+            write!(f, "{}", self.section_gap())?;
+            self.fmt_row_address(f, row)?;
+        }
+
+        if self.show_end_address {
+            write!(f, " -> ")?;
+            self.fmt_address(f, row.end_address())?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the part of a line in front of the char panel, without the padding at its end when
+    /// the char panel is `wrapped` onto the next line.
+    fn fmt_line_start(&self, f: &mut Formatter, row: &Row, wrapped: bool) -> Result {
+        if self.show_row_numbers {
+            write!(f, "{:>width$}  ", row.index + 1, width = self.decimal_row_number_width() - 2)?;
+        }
+
+        if self.show_hex_row_index {
+            write!(f, "{}  ", Hex::new(row.index, self.hex_row_index_width() - 2, self.address_uppercase))?;
+        }
+
+        if self.address_position == Position::Left {
+            self.fmt_row_address(f, row)?;
+            write!(f, "{}", if self.tab_separated { "\t" } else { self.address_suffix })?;
+        }
+
+        let right_padding = if self.compact_last_row { 0 } else { row.padding.right };
+        let ends_line = |last: bool| wrapped && last && self.row_checksum.is_none() && !self.show_class_bar;
+        let hex_padding = if ends_line(!self.show_reversed_column) { 0 } else { right_padding };
+
+        self.fmt_bytes_as_hex(f, self.word_ordered(row.padded_cells(hex_padding)), 0..row.padding.left)?;
+
+        if self.show_reversed_column {
+            let column_count = row.padded_cells(right_padding).count();
+            let shown_columns = if ends_line(self.begin_pad_fill.trim().is_empty()) { column_count - row.padding.left } else { column_count };
+            write!(f, "{}", self.section_gap())?;
+            self.fmt_bytes_as_hex(f, row.padded_cells(right_padding).rev().take(shown_columns), column_count - row.padding.left..column_count)?;
+        }
+
+        self.fmt_row_checksum(f, row)?;
+
+        if self.show_class_bar {
+            write!(f, "{}", self.section_gap())?;
+            fmt_class_bar(f, row.padded_cells(if wrapped { 0 } else { right_padding }))?;
+        }
+
+        Ok(())
+    }
+
+    fn fmt_line_od(&self, f: &mut Formatter, row: &Row) -> Result {
+        for (offset, byte) in row.cells().flatten() {
+            write!(f, " ")?;
+            self.fmt_byte_as_hex(f, offset, byte)?;
+        }
+
+        Ok(())
+    }
+
+    fn fmt_line_rtl(&self, f: &mut Formatter, row: &Row) -> Result {
+        if self.show_end_address {
+            self.fmt_address(f, row.end_address())?;
+            write!(f, " <- ")?;
+        }
+
+        let right_padding = if self.compact_last_row { 0 } else { row.padding.right };
+        let columns = row.padding.left + row.len() + right_padding;
+
+        if self.hides_char_panel(row) {
+            write!(f, "{:width$}", "", width = self.char_panel_width())?;
+        } else {
+            self.fmt_char_panel_start(f)?;
+            self.fmt_bytes_as_char(f, row.padded_cells(right_padding).rev())?;
+            self.fmt_char_panel_end(f)?;
+        }
+
+        if self.show_class_bar {
+            write!(f, "{}", self.section_gap())?;
+            fmt_class_bar(f, row.padded_cells(right_padding).rev())?;
+        }
+
+        self.fmt_row_checksum(f, row)?;
+        write!(f, "{}", self.section_gap())?;
+        self.fmt_bytes_as_hex(f, row.padded_cells(right_padding).rev(), columns - row.padding.left..columns)?;
+        write!(f, "{}", self.section_gap())?;
+
+        self.fmt_row_address(f, row)?;
+
+        if self.show_hex_row_index {
+            write!(f, "  {}", Hex::new(row.index, self.hex_row_index_width() - 2, self.address_uppercase))?;
+        }
+
+        if self.show_row_numbers {
+            write!(f, "  {:<width$}", row.index + 1, width = self.decimal_row_number_width() - 2)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the char panel of a [char_row_width](struct.HexViewBuilder.html#method.char_row_width)
+    /// wider than the row, which starts on the row and is blank on the rows it covers.
+    fn fmt_wide_char_row(&self, f: &mut Formatter, row: &Row) -> Result {
+        let width = self.char_panel_columns();
+
+        if !row.index.is_multiple_of(width / self.row_width) {
+            return write!(f, "{:width$}", "", width = width * self.char_column_width());
+        }
+
+        let data = self.shown_data();
+        let cells = (0..width - row.padding.left).map(|index| row.offset + index)
+            .map(|offset| data.get(offset).map(|byte| (offset, byte)));
+
+        self.fmt_bytes_as_char(f, (0..row.padding.left).map(|_| None).chain(cells))
+    }
+
+    fn fmt_percentage(&self, f: &mut Formatter, row: &Row) -> Result {
+        if !self.show_percentage {
+            return Ok(());
+        }
+
+        let (position, size) = match self.total_size {
+            Some(total_size) => (self.address_offset + row.offset, total_size),
+            None => (row.offset, self.shown_len()),
+        };
+        let percentage = if size == 0 { 0.0 } else { 100.0 * position as f64 / size as f64 };
+
+        write!(f, " {:>5.1}%", percentage)
+    }
+
+    fn fmt_decoded(&self, f: &mut Formatter, row: &Row) -> Result {
+        let decode_fn = match self.decode_fn {
+            Some(decode_fn) => decode_fn,
+            None => return Ok(()),
+        };
+
+        let mut texts = Vec::new();
+        let mut bytes = &row.bytes[..];
+
+        while !bytes.is_empty() {
+            match decode_fn(bytes) {
+                Some((count, text)) => {
+                    texts.push(text);
+                    bytes = &bytes[std::cmp::min(std::cmp::max(1, count), bytes.len())..];
+                }
+                None => break,
+            }
+        }
+
+        write!(f, "  {}", texts.join("; "))
+    }
+
+    fn fmt_row_checksum(&self, f: &mut Formatter, row: &Row) -> Result {
+        match self.row_checksum {
+            Some(ChecksumKind::Xor) => {
+                let checksum = row.values().fold(0, |checksum, byte| checksum ^ byte);
+                write!(f, "  {}", Hex::new(checksum as usize, 2, self.byte_uppercase))
+            }
+            Some(ChecksumKind::Sum8) => {
+                let checksum = row.values().fold(0u8, |checksum, byte| checksum.wrapping_add(byte));
+                write!(f, "  {}", Hex::new(checksum as usize, 2, self.byte_uppercase))
+            }
+            Some(ChecksumKind::Parity) => {
+                let bits: u32 = row.values().map(|byte| byte.count_ones()).sum();
+                write!(f, "  {:>2}", bits % 2)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn fmt_char_panel_start(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self.panel_divider)
+    }
+
+    fn fmt_char_panel_end(&self, f: &mut Formatter) -> Result {
+        for c in self.panel_divider.chars().rev() {
+            write!(f, "{}", c)?;
+        }
+
+        Ok(())
+    }
+
+    fn fmt_row_address(&self, f: &mut Formatter, row: &Row) -> Result {
+        if !row.index.is_multiple_of(std::cmp::max(1, self.address_every)) {
+            return write!(f, "{:width$}", "", width = self.address_width());
+        }
+
+        self.fmt_address(f, row.address)
+    }
+
+    fn fmt_address(&self, f: &mut Formatter, address: usize) -> Result {
+        if self.human_address {
+            return write!(f, "{:>width$}", human_size(address), width = self.address_width());
+        }
+
+        if let Some(shift) = self.segment_shift {
+            let offset = address & 0xFFFF;
+            let segment = (address - offset) >> shift;
+            let width = self.address_width() - 5;
+
+            let segment = Hex::new(segment, width, self.address_uppercase);
+            return write!(f, "{}:{}", segment, Hex::new(offset, 4, self.address_uppercase));
+        }
+
+        match self.address_radix {
+            Radix::Binary => write!(f, "{:0width$b}", address, width = self.address_width()),
+            Radix::Octal => write!(f, "{:0width$o}", address, width = self.address_width()),
+            Radix::Decimal => match self.address_grouping {
+                Some(separator) => {
+                    write!(f, "{:>width$}", group_thousands(address, separator), width = self.address_width())
+                }
+                None => write!(f, "{:0width$}", address, width = self.address_width()),
+            },
+            Radix::Hexadecimal => write!(f, "{}", Hex::new(address, self.address_width(), self.address_uppercase)),
+        }
+    }
+
+    fn fmt_bitfield(&self, f: &mut Formatter, offset: usize, bytes: &[u8]) -> Result {
+        write!(f, "{:width$}  7 6 5 4 3 2 1 0", "", width = self.address_column_width())?;
+
+        for (index, byte) in bytes.iter().enumerate() {
+            write!(f, "\n{:width$}", "", width = self.row_number_width())?;
+            self.fmt_address(f, self.shown_address_offset() + offset + index)?;
+            write!(f, " ")?;
+
+            for bit in (0..8).rev() {
+                write!(f, " {}", (byte >> bit) & 1)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fmt_squeeze_marker(&self, f: &mut Formatter, squeezed: usize) -> Result {
+        if self.squeeze_count {
+            write!(f, "* (repeated {} times)", squeezed)
+        } else {
+            write!(f, "*")
+        }
+    }
+
+    fn fmt_header(&self, f: &mut Formatter) -> Result {
+        if !self.show_header {
+            return Ok(());
+        }
+
+        if self.tab_separated {
+            write!(f, "\t")?;
+        } else if self.relative_addresses && self.address_position == Position::Left {
+            write!(f, "{:width$}", "", width = self.row_number_width())?;
+            self.fmt_address(f, self.address_offset)?;
+            write!(f, "{:width$}", "", width = self.address_suffix.chars().count())?;
+        } else {
+            write!(f, "{:width$}", "", width = self.hex_panel_column())?;
+        }
+
+        for column in 0..self.columns() {
+            self.fmt_column_separator(f, column)?;
+            let index = Hex::new(column % 0x100, 2, self.byte_uppercase).to_string();
+            write!(f, "{:>width$}", index, width = self.hex_column_width())?;
+        }
+
+        if self.show_reversed_column {
+            write!(f, "{}{:width$}", self.section_gap(), "", width = self.hex_panel_width())?;
+        }
+
+        if self.row_checksum.is_some() {
+            write!(f, "    ")?;
+        }
+
+        if self.show_class_bar {
+            write!(f, "{}{:width$}", self.section_gap(), "", width = self.columns())?;
+        }
+
+        write!(f, "{}{:width$}", self.section_gap(), "", width = self.char_panel_padding())?;
+        self.fmt_char_panel_start(f)?;
+
+        for column in 0..self.char_panel_columns() {
+            let index = Hex::new(column % 0x10, 1, self.byte_uppercase).to_string();
+            write!(f, "{:width$}", index, width = self.char_column_width())?;
+        }
+
+        self.fmt_char_panel_end(f)?;
+        writeln!(f)?;
+
+        if self.header_tick != 0 {
+            self.fmt_column_marks(f, (0..self.columns()).map(|column| column % self.header_tick == 0))?;
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `^^` under every hex column for which `marks` yields true.
+    fn fmt_column_marks<I: Iterator<Item = bool>>(&self, f: &mut Formatter, marks: I) -> Result {
+        let mut line = String::new();
+
+        for (column, mark) in marks.enumerate() {
+            line.push_str(&" ".repeat(self.column_separator_width(column)));
+            line.push_str(&format!("{:>width$}", if mark { "^^" } else { "" }, width = self.hex_column_width()));
+        }
+
+        write!(f, "{:width$}{}", "", line.trim_end(), width = self.hex_panel_column())
+    }
+
+    /// Writes the bytes of the row as signed decimals, aligned under the hex columns.
+    fn fmt_signed_decimals(&self, f: &mut Formatter, row: &Row) -> Result {
+        if !self.stacked_signed_decimal {
+            return Ok(());
+        }
+
+        let mut line = String::new();
+
+        for (column, cell) in row.cells().enumerate() {
+            let value = cell.map_or(String::new(), |(_, byte)| (byte as i8).to_string());
+            line.push_str(&" ".repeat(self.column_separator_width(column)));
+            line.push_str(&format!("{:>width$}", value, width = self.hex_column_width()));
+        }
+
+        write!(f, "\n{:width$}{}", "", line.trim_end(), width = self.hex_panel_column())
+    }
+
+    fn fmt_underline(&self, f: &mut Formatter, row: &Row) -> Result {
+        let is_underlined = |offset: usize| {
+            self.underlines.iter().any(|range| range.contains(&offset))
+                || (!self.use_color && self.differs_from_previous_row(offset))
+        };
+
+        if !row.cells().flatten().any(|(offset, _)| is_underlined(offset)) {
+            return Ok(());
+        }
+
+        writeln!(f)?;
+        self.fmt_column_marks(f, row.cells().map(|cell| cell.is_some_and(|(offset, _)| is_underlined(offset))))
+    }
+
+    fn fmt_footer(&self, f: &mut Formatter) -> Result {
+        let omitted = self.data.len() - self.shown_len();
+
+        if omitted != 0 {
+            match self.omit_message {
+                Some(omit_message) => write!(f, "\n{}", omit_message(omitted))?,
+                None => write!(f, "\n... (+{} bytes)", omitted)?,
+            }
+        }
+
+        if self.show_final_offset && (!self.data.is_empty() || self.empty_shows_address) {
+            writeln!(f)?;
+            self.fmt_address(f, self.shown_address_offset() + self.data.len())?;
+        }
+
+        if let Some(ref range) = self.bitfield {
+            let end = std::cmp::min(range.end, self.data.len());
+            let start = std::cmp::min(range.start, end);
+            writeln!(f)?;
+            self.fmt_bitfield(f, start, &self.data.slice(start..end))?;
+        }
+
+        if !self.tags.is_empty() {
+            writeln!(f)?;
+            fmt_tags(f, &self.tags)?;
+        }
+
+        if self.show_codepage_legend {
+            self.fmt_codepage_legend(f)?;
+        }
+
+        Ok(())
+    }
+
+    fn fmt_codepage_legend(&self, f: &mut Formatter) -> Result {
+        let mut used = [false; 256];
+
+        for byte in self.shown_data().iter() {
+            used[byte as usize] = true;
+        }
+
+        for byte in (0..256).filter(|&byte| used[byte]) {
+            let glyph = byte_mapping::as_char(byte as u8, self.codepage);
+
+            if !glyph.is_ascii() && glyph != byte_mapping::NIL {
+                write!(f, "\n{}  {}", Hex::new(byte, 2, self.byte_uppercase), glyph)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> std::fmt::Display for HexView<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.row_width == 0 {
+            return write!(f, "Invalid HexView::width");
+        }
+
+        if !self.gaps.is_empty() {
+            return self.fmt_gaps(f);
+        }
+
+        let view = self.laid_out();
+        let prefix = format!("{:indent$}{}", "", view.line_prefix, indent = view.indent_level * view.indent_width);
+
+        if view.line_continuation || !prefix.is_empty() {
+            let mut lines = DecoratedLines { inner: f, prefix, continuation: view.line_continuation, line_start: true };
+            return write!(lines, "{}", LinesDisplay { view: &view });
+        }
+
+        view.fmt_lines(f)
+    }
+}
+
+impl<'a> HexView<'a> {
+    fn fmt_gaps(&self, f: &mut Formatter) -> Result {
+        let mut regions = self.gap_regions();
+
+        // A gap in front of all data is shown below the title and header of the first region.
+        if let Some(&(0, len)) = self.gaps.first() {
+            let address_width = regions.iter().map(|region| region.address_width()).max().unwrap_or(0);
+            let mut first = regions[0].laid_out();
+            first.min_address_width = std::cmp::max(first.min_address_width, address_width);
+
+            if let Some(title) = first.title {
+                writeln!(f, "{}", title)?;
+            }
+
+            first.fmt_header(f)?;
+            writeln!(f, "... gap of {} bytes ...", len)?;
+
+            regions[0].title = None;
+            regions[0].show_header = false;
+        }
+
+        write!(f, "{}", MultiHexView::new(regions))
+    }
+
+    fn fmt_strings(&self, f: &mut Formatter, min_len: usize) -> Result {
+        let data = self.shown_data().joined();
+        let mut separator = "";
+        let mut start = 0;
+
+        for (offset, &byte) in data.iter().chain(std::iter::once(&0)).enumerate() {
+            if byte_mapping::is_printable_ascii(byte) {
+                continue;
+            }
+
+            if offset - start >= std::cmp::max(1, min_len) {
+                write!(f, "{}", separator)?;
+                self.fmt_address(f, self.shown_address_offset() + start)?;
+                write!(f, "{}", self.address_suffix)?;
+
+                for &byte in data[start..offset].iter() {
+                    write!(f, "{}", byte as char)?;
+                }
+
+                separator = "\n";
+            }
+
+            start = offset + 1;
+        }
+
+        Ok(())
+    }
+
+    fn fmt_lines(&self, f: &mut Formatter) -> Result {
+        if let Some(min_len) = self.strings_min_len {
+            return self.fmt_strings(f, min_len);
+        }
+
+        if let Some(title) = self.title {
+            writeln!(f, "{}", title)?;
+        }
+
+        self.fmt_header(f)?;
+
+        if self.cell_borders {
+            self.fmt_cell_border(f, '┬')?;
+            writeln!(f)?;
+        }
+
+        let mut separator = "";
+
+        for line in self.row_lines() {
+            write!(f, "{}", separator)?;
+
+            match line {
+                RowLine::Row(row) => {
+                    self.fmt_line(f, &row)?;
+                    self.fmt_signed_decimals(f, &row)?;
+                    self.fmt_underline(f, &row)?;
+                }
+                RowLine::Squeezed(squeezed) => self.fmt_squeeze_marker(f, squeezed)?,
+            }
+
+            separator = "\n";
+        }
+
+        if self.cell_borders {
+            write!(f, "{}", separator)?;
+            self.fmt_cell_border(f, '┴')?;
+        }
+
+        self.fmt_footer(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_lines_but_the_last_can_be_continued() {
+        let data: Vec<u8> = (0x41..0x41 + 6).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .line_continuation(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00000000  41 42 43 44  | ABCD |\\"), lines.next());
+        assert_eq!(Some("00000004  45 46        | EF   |"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn addresses_can_be_printed_as_human_readable_sizes() {
+        let data = [b'a'; 8];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(1020)
+            .row_width(4)
+            .human_address(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("    1020  61 61 61 61  | aaaa |"), lines.next());
+        assert_eq!(Some("    1.0K  61 61 61 61  | aaaa |"), lines.next());
+        assert_eq!("2.5M", super::human_size(5 << 19));
+    }
+
+    #[test]
+    fn the_row_width_can_change_within_the_data() {
+        let data: Vec<u8> = (0x41..0x41 + 26).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(8)
+            .width_change(8, 16)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00000000  41 42 43 44 45 46 47 48                          | ABCDEFGH         |"), lines.next());
+        assert_eq!(Some("00000008  49 4A 4B 4C 4D 4E 4F 50 51 52 53 54 55 56 57 58  | IJKLMNOPQRSTUVWX |"), lines.next());
+        assert_eq!(Some("00000018  59 5A                                            | YZ               |"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn a_width_change_to_zero_still_advances_the_rows() {
+        let data: Vec<u8> = (0x41..0x41 + 6).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .width_change(4, 0)
+            .finish();
+
+        assert_eq!(3, format!("{}", row_view).lines().count());
+    }
+
+    #[test]
+    fn a_width_change_narrower_than_the_begin_padding_holds_a_byte() {
+        let data: Vec<u8> = (0x41..0x41 + 4).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(5)
+            .width_change(0, 2)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert!(lines.next().unwrap().starts_with("00000000                 41  "));
+        assert!(lines.next().unwrap().starts_with("00000006  42 43  "));
+        assert!(lines.next().unwrap().starts_with("00000008  44  "));
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn control_bytes_can_be_shown_in_caret_notation() {
+        let data = [0x01, 0x41, 0x7F];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .caret_notation(true)
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "00000000  01 41 7F     | ^AA ^?   |");
+    }
+
+    #[test]
+    fn high_bytes_widen_the_caret_notation_columns() {
+        let data = [0x01, 0xC1];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(2)
+            .caret_notation(true)
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "00000000  01 C1  | ^A  M-A  |");
+    }
+
+    #[test]
+    fn only_the_strings_can_be_listed() {
+        let data = b"\x00\x01HELLO\xFF\x02ab\x00";
+
+        let row_view = HexViewBuilder::new(data)
+            .address_offset(0x100)
+            .strings_only(4)
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "00000102  HELLO");
+    }
+
+    #[test]
+    fn the_char_panel_can_be_wider_than_the_hex_panel() {
+        let data: Vec<u8> = (0x41..0x41 + 20).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(8)
+            .char_row_width(16)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00000000  41 42 43 44 45 46 47 48  | ABCDEFGHIJKLMNOP |"), lines.next());
+        assert_eq!(Some("00000008  49 4A 4B 4C 4D 4E 4F 50  |                  |"), lines.next());
+        assert_eq!(Some("00000010  51 52 53 54              | QRST             |"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn a_char_row_width_is_rounded_down_to_a_multiple_of_the_row_width() {
+        let data: Vec<u8> = (0x41..0x41 + 20).collect();
+
+        let multiple_view = HexViewBuilder::new(&data).row_width(8).char_row_width(16).finish();
+        let non_multiple_view = HexViewBuilder::new(&data).row_width(8).char_row_width(20).finish();
+
+        assert_eq!(format!("{}", multiple_view), format!("{}", non_multiple_view));
+    }
+
+    #[test]
+    fn the_address_range_starts_at_the_address_offset() {
+        let data = [0; 10];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(0x20)
+            .finish();
+
+        assert_eq!(0x20..0x2A, row_view.address_range());
+    }
+
+    #[test]
+    fn the_address_range_follows_the_shown_addresses() {
+        let data = [0; 10];
+
+        let relative = HexViewBuilder::new(&data).address_offset(0x20).relative_addresses(true).finish();
+        let limited = HexViewBuilder::new(&data).address_offset(0x20).limit(4).finish();
+        let at_the_end = HexViewBuilder::new(&data).address_offset(usize::MAX - 4).finish();
+        let last = (usize::MAX - 4) as u64;
+
+        assert_eq!(0..10, relative.address_range());
+        assert_eq!(0x20..0x24, limited.address_range());
+        assert_eq!(last..last.saturating_add(10), at_the_end.address_range());
+    }
+
+    #[test]
+    fn the_codepage_legend_lists_the_used_glyphs() {
+        let data = [0xC4, 0x41, 0xB3, 0x00, 0xC4];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(8)
+            .codepage(byte_mapping::CODEPAGE_0850)
+            .show_codepage_legend(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines().skip(1);
+
+        assert_eq!(Some("B3  │"), lines.next());
+        assert_eq!(Some("C4  ─"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn signed_decimals_can_be_stacked_under_the_bytes() {
+        let data = [0x41, 0xFF, 0x80, 0x7F, 0x00];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .codepage(byte_mapping::CODEPAGE_ASCII)
+            .stacked_signed_decimal(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00000000    41   FF   80   7F  | A... |"), lines.next());
+        assert_eq!(Some("            65   -1 -128  127"), lines.next());
+        assert_eq!(Some("00000004    00                 | .    |"), lines.next());
+        assert_eq!(Some("             0"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn addresses_can_be_relative_to_the_address_offset() {
+        let data: Vec<u8> = (0x41..0x41 + 6).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(0x4002)
+            .row_width(4)
+            .relative_addresses(true)
+            .show_header(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00004002  00 01 02 03  | 0123 |"), lines.next());
+        assert_eq!(Some("00000000  41 42 43 44  | ABCD |"), lines.next());
+        assert_eq!(Some("00000004  45 46        | EF   |"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn the_bytes_changed_since_the_previous_snapshot_are_colored() {
+        let previous = [0x41, 0x42, 0x43, 0x44];
+        let current = [0x41, 0x62, 0x43, 0x64];
+
+        let row_view = HexViewBuilder::new(&current)
+            .row_width(4)
+            .finish();
+
+        assert_eq!(row_view.diff_render(&previous), "00000000  41 \x1b[31m62\x1b[0m 43 \x1b[31m64\x1b[0m  | A\x1b[31mb\x1b[0mC\x1b[31md\x1b[0m |");
+        assert_eq!(row_view.diff_render(&current), format!("{}", row_view));
+    }
+
+    #[test]
+    fn the_decoded_texts_follow_the_row() {
+        let data = [0x90; 6];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .codepage(byte_mapping::CODEPAGE_ASCII)
+            .decode_fn(|bytes| if bytes.len() >= 2 { Some((2, "NOP".to_string())) } else { None })
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00000000  90 90 90 90  | .... |  NOP; NOP"), lines.next());
+        assert_eq!(Some("00000004  90 90        | ..   |  NOP"), lines.next());
+    }
+
+    #[test]
+    fn the_address_can_be_printed_on_the_right() {
+        let data: Vec<u8> = (0x41..0x41 + 6).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .address_position(Position::Right)
+            .show_header(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00 01 02 03  | 0123 |"), lines.next());
+        assert_eq!(Some("41 42 43 44  | ABCD |  00000000"), lines.next());
+        assert_eq!(Some("45 46        | EF   |  00000004"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn the_bytes_without_a_glyph_are_counted() {
+        let data = [0x00, 0x41, 0x01, 0x2E, 0x1F, 0x42, 0x7F, 0x43];
+
+        let ascii_view = HexViewBuilder::new(&data).codepage(byte_mapping::CODEPAGE_ASCII).finish();
+        let cp850_view = HexViewBuilder::new(&data).finish();
+
+        assert_eq!(4, ascii_view.non_printable_count());
+        assert_eq!(1, cp850_view.non_printable_count());
+    }
+
+    #[test]
+    fn every_line_is_indented_by_the_indent_level() {
+        let data: Vec<u8> = (0x41..0x41 + 6).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .show_header(true)
+            .indent_level(2)
+            .indent_width(2)
+            .line_prefix("# ")
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("    #           00 01 02 03  | 0123 |"), lines.next());
+        assert_eq!(Some("    # 00000000  41 42 43 44  | ABCD |"), lines.next());
+        assert_eq!(Some("    # 00000004  45 46        | EF   |"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn the_view_can_be_split_into_pages() {
+        let data: Vec<u8> = (0..64).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(16)
+            .finish();
+
+        let pages: Vec<_> = row_view.pages(2).collect();
+
+        assert_eq!(2, pages.len());
+        assert!(format!("{}", pages[0]).starts_with("00000000  00 01"));
+        assert!(format!("{}", pages[1]).starts_with("00000020  20 21"));
+        assert_eq!(2, format!("{}", pages[1]).lines().count());
+    }
+
+    #[test]
+    fn a_view_with_a_zero_row_width_is_a_single_page() {
+        let data = [b'a'; 40];
+
+        let view = HexViewBuilder::new(&data)
+            .row_width(0)
+            .finish();
+
+        let pages: Vec<_> = view.pages(2).collect();
+
+        assert_eq!(1, pages.len());
+        assert_eq!("Invalid HexView::width", format!("{}", pages[0]));
+    }
+
+    #[test]
+    fn an_unaligned_view_is_paged_at_the_row_boundaries() {
+        let data: Vec<u8> = (0..20).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(2)
+            .row_width(4)
+            .finish();
+
+        let addresses: Vec<_> = row_view.pages(2).map(|page| page.address_range()).collect();
+
+        assert_eq!(vec![2..8, 8..16, 16..22], addresses);
+    }
+
+    #[test]
+    fn the_hex_row_index_is_printed_in_front_of_the_address() {
+        let data: Vec<u8> = (0x41..0x41 + 10).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .show_hex_row_index(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("0000  00000000  41 42 43 44  | ABCD |"), lines.next());
+        assert_eq!(Some("0001  00000004  45 46 47 48  | EFGH |"), lines.next());
+        assert_eq!(Some("0002  00000008  49 4A        | IJ   |"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn the_char_panel_of_a_row_without_glyphs_can_be_hidden() {
+        let data = [0x00, 0x00, 0x00, 0x00, 0x00, 0x41];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .hide_empty_char_panel(true)
+            .show_end_address(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00000000  00 00 00 00           -> 00000004"), lines.next());
+        assert_eq!(Some("00000004  00 41        | \u{E000}A   | -> 00000006"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn the_percentage_is_relative_to_the_total_size() {
+        let data = [b'a'; 16];
+
+        let slice_view = HexViewBuilder::new(&data)
+            .address_offset(800)
+            .row_width(8)
+            .show_percentage(true)
+            .finish();
+        let file_view = HexViewBuilder::new(&data)
+            .address_offset(800)
+            .row_width(8)
+            .show_percentage(true)
+            .total_size(1600)
+            .finish();
+
+        let slice_result = format!("{}", slice_view);
+        let file_result = format!("{}", file_view);
+
+        assert_eq!(vec!["   0.0%", "  50.0%"], slice_result.lines().map(|line| &line[line.len() - 7..]).collect::<Vec<_>>());
+        assert_eq!(vec!["  50.0%", "  50.5%"], file_result.lines().map(|line| &line[line.len() - 7..]).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn the_percentage_is_relative_to_the_shown_data() {
+        let data = [b'a'; 32];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(8)
+            .limit(16)
+            .show_percentage(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(vec!["   0.0%", "  50.0%"], result.lines().take(2).map(|line| &line[line.len() - 7..]).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn little_endian_words_are_reversed_in_both_panels() {
+        let data = b"ABCDEF";
+
+        let hex_only_view = HexViewBuilder::new(data)
+            .row_width(8)
+            .word_size(2)
+            .word_endian(Endian::Little)
+            .finish();
+        let mirrored_view = HexViewBuilder::new(data)
+            .row_width(8)
+            .word_size(2)
+            .word_endian(Endian::Little)
+            .mirror_char_panel(true)
+            .finish();
+
+        assert_eq!(format!("{}", hex_only_view), "00000000  42 41  44 43  46 45         | ABCDEF   |");
+        assert_eq!(format!("{}", mirrored_view), "00000000  42 41  44 43  46 45         | BADCFE   |");
+    }
+
+    #[test]
+    fn the_group_size_is_kept_whatever_the_word_size_order() {
+        let data = b"ABCDEFGH";
+
+        let words_first = HexViewBuilder::new(data).row_width(8).word_size(2).group_size(4).finish();
+        let groups_first = HexViewBuilder::new(data).row_width(8).group_size(4).word_size(2).finish();
+        let elements_first = HexViewBuilder::new(data).row_width(8).element_size(4).word_size(2).finish();
+
+        let expected = "00000000  41 42 43 44  45 46 47 48  | ABCDEFGH |";
+
+        assert_eq!(expected, format!("{}", words_first));
+        assert_eq!(expected, format!("{}", groups_first));
+        assert_eq!(expected, format!("{}", elements_first));
+    }
+
+    #[test]
+    fn the_final_offset_is_printed_on_the_last_line() {
+        let data = [0x41; 1024];
+
+        let row_view = HexViewBuilder::new(&data)
+            .show_final_offset(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(65, result.lines().count());
+        assert_eq!(Some("00000400"), result.lines().last());
+    }
+
+    #[test]
+    fn the_padding_of_the_char_panel_can_be_marked() {
+        let data = b"ab ";
+
+        let row_view = HexViewBuilder::new(data)
+            .address_offset(1)
+            .row_width(6)
+            .mark_padding('·')
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "00000000     61 62 20        | ·ab ·· |");
+    }
+
+    #[test]
+    fn the_bytes_outside_the_focus_are_dimmed() {
+        let data: Vec<u8> = (0x41..0x41 + 12).collect();
+
+        let plain_view = HexViewBuilder::new(&data)
+            .row_width(12)
+            .focus(&[2..4, 8..10])
+            .finish();
+        let colored_view = HexViewBuilder::new(&data[..3])
+            .row_width(3)
+            .use_color(true)
+            .focus(&[2..3, 8..10])
+            .finish();
+
+        assert_eq!(format!("{}", plain_view), "00000000  41 42 43 44 45 46 47 48 49 4A 4B 4C  | ..CD....IJ.. |");
+        assert_eq!(format!("{}", colored_view), "00000000  \x1b[2m41\x1b[0m \x1b[2m42\x1b[0m 43  | \x1b[2mA\x1b[0m\x1b[2mB\x1b[0mC |");
+    }
+
+    #[test]
+    fn highlights_outside_the_focus_are_not_dimmed() {
+        let data = [0x41, 0x42, 0x43];
+
+        let view = HexViewBuilder::new(&data)
+            .row_width(3)
+            .use_color(true)
+            .focus(&[2..3, 8..10])
+            .highlight(0..1, Color::Red)
+            .finish();
+
+        assert_eq!(format!("{}", view), "00000000  \x1b[31m41\x1b[0m \x1b[2m42\x1b[0m 43  | \x1b[31mA\x1b[0m\x1b[2mB\x1b[0mC |");
+    }
+
+    #[test]
+    fn the_bytes_can_be_separated_by_cell_borders() {
+        let data = b"ABCDE";
+
+        let view = HexViewBuilder::new(data)
+            .row_width(4)
+            .cell_borders(true)
+            .finish();
+
+        let result = format!("{}", view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("          ───┬────┬────┬───"), lines.next());
+        assert_eq!(Some("00000000  41 │ 42 │ 43 │ 44  | ABCD |"), lines.next());
+        assert_eq!(Some("00000004  45 │    │    │     | E    |"), lines.next());
+        assert_eq!(Some("          ───┴────┴────┴───"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn the_nibbles_can_be_colored_separately() {
+        let data = [0x4A, 0x4B];
+
+        let view = HexViewBuilder::new(&data)
+            .row_width(2)
+            .use_color(true)
+            .nibble_color_fn(|_, _| (Some(Color::Red), None))
+            .finish();
+
+        assert_eq!(format!("{}", view), "00000000  \x1b[31m4\x1b[0mA \x1b[31m4\x1b[0mB  | JK |");
+    }
+
+    #[test]
+    fn the_od_style_matches_od() {
+        let text_view = HexViewBuilder::new(b"Hello, world!\n").od_style().finish();
+        let zeros = [0; 40];
+        let zeros_view = HexViewBuilder::new(&zeros).od_style().finish();
+
+        assert_eq!(format!("{}", text_view), " 48 65 6c 6c 6f 2c 20 77 6f 72 6c 64 21 0a");
+        assert_eq!(format!("{}", zeros_view), format!(" {}\n*\n {}", ["00"; 16].join(" "), ["00"; 8].join(" ")));
+    }
+
+    #[test]
+    fn the_reversed_bytes_can_follow_the_hex_panel() {
+        let data = [0x41, 0x42, 0x43];
+
+        let view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .show_reversed_column(true)
+            .finish();
+
+        assert_eq!(format!("{}", view), "00000000  41 42 43        43 42 41  | ABC  |");
+        let control_view = HexViewBuilder::new(&[0x01, 0x02, 0x03]).row_width(4).show_reversed_column(true).finish();
+        assert!(format!("{}", control_view).starts_with("00000000  01 02 03        03 02 01  | "));
+    }
+
+    #[test]
+    fn the_bytes_can_be_shown_as_inline_ascii() {
+        let data = [0x41, 0x00, 0x27];
+
+        let view = HexViewBuilder::new(&data)
+            .row_width(3)
+            .codepage(byte_mapping::CODEPAGE_ASCII)
+            .inline_ascii(true)
+            .finish();
+
+        assert_eq!(format!("{}", view), "00000000   'A' \\x00 \\x27  | A.' |");
+    }
+
+    #[test]
+    fn test_begin_padding() {
+        // Rust 1.13 needs the fully qualified name here
+        assert_eq!(super::calculate_begin_padding(0, 16), 0);
+        assert_eq!(super::calculate_begin_padding(16, 16), 0);
+        assert_eq!(super::calculate_begin_padding(54, 16), 6);
+    }
+
+    #[test]
+    fn a_full_line_is_formatted_as_expected() {
+        let data: Vec<u8> = (0x40..0x40 + 0xF + 1).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(data.len())
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(result, "00000000  40 41 42 43 44 45 46 47 48 49 4A 4B 4C 4D 4E 4F  | @ABCDEFGHIJKLMNO |");
+    }
+
+    #[test]
+    fn an_incomplete_line_is_padded_on_the_right() {
+        let data = [b'a'; 10];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(16)
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(result, "00000000  61 61 61 61 61 61 61 61 61 61                    | aaaaaaaaaa       |");
+    }
+
+    #[test]
+    fn a_compact_last_row_has_no_padding_columns() {
+        let data: Vec<u8> = (0x41..0x41 + 7).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .compact_last_row(true)
+            .finish();
+
+        let expected = "00000000  41 42 43 44  | ABCD |\n00000004  45 46 47  | EFG |";
+
+        assert_eq!(expected, format!("{}", row_view));
+    }
+
+    #[test]
+    fn the_char_panel_of_a_short_row_is_aligned_by_default() {
+        let row_view = HexViewBuilder::new(&[0x41, 0x42, 0x43])
+            .row_width(4)
+            .finish();
+
+        assert_eq!("00000000  41 42 43     | ABC  |", format!("{}", row_view));
+    }
+
+    #[test]
+    fn the_char_panel_of_a_short_row_can_be_left_unaligned() {
+        let row_view = HexViewBuilder::new(&[0x41, 0x42, 0x43])
+            .row_width(4)
+            .align_char_panel(false)
+            .finish();
+
+        assert_eq!("00000000  41 42 43     | ABC |", format!("{}", row_view));
+    }
+
+    #[test]
+    fn empty_data_shows_the_address_of_its_row_by_default() {
+        let row_view = HexViewBuilder::new(&[])
+            .address_offset(0x25)
+            .row_width(16)
+            .finish();
+
+        let expected = format!("00000020  {:47}  | {:16} |", "", "");
+
+        assert_eq!(expected, format!("{}", row_view));
+    }
+
+    #[test]
+    fn empty_data_can_show_nothing() {
+        let row_view = HexViewBuilder::new(&[])
+            .address_offset(0x25)
+            .row_width(16)
+            .empty_shows_address(false)
+            .finish();
+
+        assert_eq!("", format!("{}", row_view));
+    }
+
+    #[test]
+    fn an_unaligned_address_causes_padded_on_the_left() {
+        let data = [b'a'; 11];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(5)
+            .row_width(16)
+            .finish();
+
+        let result = format!("{}", row_view);
+        println!("{}", result);
+
+        assert_eq!(result, "00000000                 61 61 61 61 61 61 61 61 61 61 61  |      aaaaaaaaaaa |");
+    }
+
+    #[test]
+    fn the_begin_padding_can_be_filled() {
+        let data: Vec<u8> = (0x40..0x40 + 3).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(5)
+            .row_width(8)
+            .begin_pad_fill("~~")
+            .finish();
+
+        let expected = "00000000  ~~ ~~ ~~ ~~ ~~ 40 41 42  |      @AB |";
+
+        assert_eq!(expected, format!("{}", row_view));
+    }
+
+    #[test]
+    fn an_unaligned_incomplete_line_causes_padding_on_both_sides() {
+        let data = [b'a'; 8];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(5)
+            .row_width(16)
+            .finish();
+
+        let result = format!("{}", row_view);
+        println!("{}", result);
+
+        assert_eq!(result, "00000000                 61 61 61 61 61 61 61 61           |      aaaaaaaa    |");
+    }
+
+    #[test]
+    fn aligned_addresses_follow_the_row_grid() {
+        let data = [b'a'; 20];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(5)
+            .align_addresses(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00000000                 61 61 61 61 61 61 61 61 61 61 61  |      aaaaaaaaaaa |"), lines.next());
+        assert_eq!(Some("00000010  61 61 61 61 61 61 61 61 61                       | aaaaaaaaa        |"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn the_first_row_can_be_aligned_independently_of_the_row_width() {
+        let data = [b'a'; 6];
+
+        let wide_alignment_view = HexViewBuilder::new(&data)
+            .address_offset(20)
+            .row_width(8)
+            .align_to(16)
+            .finish();
+        let narrow_alignment_view = HexViewBuilder::new(&data)
+            .address_offset(28)
+            .row_width(16)
+            .align_to(8)
+            .finish();
+
+        assert_eq!(format!("{}", wide_alignment_view), "00000010              61 61 61 61  |     aaaa |\n00000018  61 61                    | aa       |");
+        assert_eq!(format!("{}", narrow_alignment_view), "00000018              61 61 61 61 61 61                    |     aaaaaa       |");
+    }
+
+    #[test]
+    fn unaligned_addresses_follow_the_byte_offsets() {
+        let data = [b'a'; 20];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(5)
+            .align_addresses(false)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00000005  61 61 61 61 61 61 61 61 61 61 61 61 61 61 61 61  | aaaaaaaaaaaaaaaa |"), lines.next());
+        assert_eq!(Some("00000015  61 61 61 61                                      | aaaa             |"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn the_char_panel_wraps_when_a_line_exceeds_the_max_width() {
+        let data: Vec<u8> = (0x40..0x40 + 0xF + 1).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .max_width(40)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00000000  40 41 42 43 44 45 46 47 48 49 4A 4B 4C 4D 4E 4F"), lines.next());
+        assert_eq!(Some("          | @ABCDEFGHIJKLMNO |"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn a_wrapped_short_row_has_no_trailing_spaces() {
+        let data: Vec<u8> = (0x40..0x40 + 20).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .max_width(40)
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(Some("00000010  50 51 52 53"), result.lines().nth(2));
+        assert!(result.lines().all(|line| line == line.trim_end()));
+    }
+
+    #[test]
+    fn a_wrapped_short_row_ends_with_its_last_byte() {
+        let data: Vec<u8> = (0x40..0x40 + 20).collect();
+
+        let colored = HexViewBuilder::new(&data).max_width(40).use_color(true).highlight(19..20, Color::Red).finish();
+        let class_bar = HexViewBuilder::new(&data).max_width(40).show_class_bar(true).finish();
+        let reversed = HexViewBuilder::new(&data).max_width(40).show_reversed_column(true).finish();
+
+        assert_eq!(Some("00000010  50 51 52 \x1b[31m53\x1b[0m"), format!("{}", colored).lines().nth(2));
+        assert!(format!("{}", class_bar).lines().nth(2).unwrap().ends_with("  ...."));
+        assert!(format!("{}", reversed).lines().nth(2).unwrap().ends_with("  53 52 51 50"));
+    }
+
+    #[test]
+    fn right_to_left_and_od_lines_are_not_wrapped() {
+        let data: Vec<u8> = (0x40..0x40 + 0xF + 1).collect();
+
+        let rtl = HexViewBuilder::new(&data).max_width(40).rtl(true).finish();
+        let od = HexViewBuilder::new(&data).max_width(40).od_style().finish();
+
+        assert_eq!(1, format!("{}", rtl).lines().count());
+        assert_eq!(1, format!("{}", od).lines().count());
+    }
+
+    #[test]
+    fn the_char_panel_can_start_at_a_fixed_column() {
+        let data: Vec<u8> = (0x41..0x41 + 8).collect();
+
+        let narrow = HexViewBuilder::new(&data).row_width(4).char_panel_column(40).finish();
+        let wide = HexViewBuilder::new(&data).row_width(8).char_panel_column(40).finish();
+
+        let narrow = format!("{}", narrow);
+        let wide = format!("{}", wide);
+
+        assert_eq!(Some(40), narrow.lines().next().unwrap().find('|'));
+        assert_eq!(Some(40), wide.lines().next().unwrap().find('|'));
+    }
+
+    #[test]
+    fn a_narrow_char_panel_column_is_ignored() {
+        let view = HexViewBuilder::new(&[0x41]).row_width(4).char_panel_column(2).finish();
+
+        assert_eq!("00000000  41           | A    |", format!("{}", view));
+    }
+
+    #[test]
+    fn a_line_within_the_max_width_is_not_wrapped() {
+        let data: Vec<u8> = (0x40..0x40 + 0xF + 1).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .max_width(80)
+            .finish();
+
+        assert_eq!(1, format!("{}", row_view).lines().count());
+    }
+
+    #[test]
+    fn the_color_fn_colors_the_classified_bytes() {
+        let data = [0x41, 0x42, 0x43, 0x44, 0x45, 0x46];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(6)
+            .use_color(true)
+            .color_fn(|offset, _| if offset % 2 == 0 { Some(Color::Blue) } else { None })
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(result, "00000000  \x1b[34m41\x1b[0m 42 \x1b[34m43\x1b[0m 44 \x1b[34m45\x1b[0m 46  \
+                            | \x1b[34mA\x1b[0mB\x1b[34mC\x1b[0mD\x1b[34mE\x1b[0mF |");
+    }
+
+    #[test]
+    fn the_color_fn_is_ignored_without_use_color() {
+        let data = [0x41, 0x42];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(2)
+            .color_fn(|_, _| Some(Color::Red))
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "00000000  41 42  | AB |");
+    }
+
+    #[test]
+    fn the_mac_roman_codepage_can_be_used_for_the_char_panel() {
+        let data = [0xA5];
+
+        let row_view = HexViewBuilder::new(&data)
+            .codepage(byte_mapping::CODEPAGE_MAC_ROMAN)
+            .row_width(1)
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "00000000  A5  | • |");
+    }
+
+    #[test]
+    fn every_line_is_passed_to_the_callback() {
+        let data: Vec<u8> = (0..40).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .show_header(true)
+            .tag(3, 'a')
+            .finish();
+
+        let mut lines = Vec::new();
+        row_view.for_each_line(|line| lines.push(line.to_string()));
+
+        assert_eq!(format!("{}", row_view).lines().collect::<Vec<_>>(), lines);
+    }
+
+    #[test]
+    fn a_single_row_can_be_rendered_by_index() {
+        let data: Vec<u8> = (0..40).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(5)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let lines: Vec<&str> = result.lines().collect();
+
+        assert_eq!(Some(lines[0]), row_view.render_row(0).as_deref());
+        assert_eq!(Some(lines[1]), row_view.render_row(1).as_deref());
+        assert_eq!(Some(lines[2]), row_view.render_row(2).as_deref());
+        assert!(row_view.render_row(0).unwrap().starts_with("00000000                 00 01 02"));
+        assert_eq!(None, row_view.render_row(3));
+    }
+
+    #[test]
+    fn a_codepage_with_a_wide_char_is_rejected() {
+        let mut codepage = byte_mapping::CODEPAGE_ASCII.to_vec();
+        codepage[0x41] = '漢';
+
+        assert_eq!(Some(0x41), HexViewBuilder::new(&[0x41]).try_codepage(&codepage).err());
+        assert!(HexViewBuilder::new(&[0x41]).try_codepage(byte_mapping::CODEPAGE_ASCII).is_ok());
+    }
+
+    #[test]
+    fn a_sanitized_char_panel_replaces_combining_chars() {
+        let mut codepage = byte_mapping::CODEPAGE_ASCII.to_vec();
+        codepage[0x41] = '\u{0301}';
+
+        let row_view = HexViewBuilder::new(&[0x41, 0x42])
+            .row_width(2)
+            .codepage(&codepage)
+            .sanitize_char_panel(true)
+            .non_printable_char('?')
+            .finish();
+
+        assert_eq!("00000000  41 42  | ?B |", format!("{}", row_view));
+    }
+
+    #[test]
+    fn a_sanitized_utf16_char_panel_replaces_wide_chars() {
+        let data = [0x2D, 0x4E, 0x41, 0x00];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .utf16_char_panel(Endian::Little)
+            .sanitize_char_panel(true)
+            .non_printable_char('?')
+            .finish();
+
+        assert_eq!("00000000  2D 4E 41 00  | ? A  |", format!("{}", row_view));
+    }
+
+    #[test]
+    fn a_sanitized_caret_notation_is_unchanged() {
+        let data = [0x01, 0x41, 0xC1];
+
+        let plain = HexViewBuilder::new(&data).row_width(3).caret_notation(true).finish();
+        let sanitized = HexViewBuilder::new(&data).row_width(3).caret_notation(true).sanitize_char_panel(true).finish();
+
+        assert_eq!(format!("{}", plain), format!("{}", sanitized));
+    }
+
+    #[test]
+    fn lowercase_digits_can_be_used() {
+        let data = [0xAB, 0xCD];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(0xEF)
+            .row_width(1)
+            .uppercase(false)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("000000ef  ab  | ½ |"), lines.next());
+        assert_eq!(Some("000000f0  cd  | ═ |"), lines.next());
+    }
+
+    #[test]
+    fn the_bytes_can_have_a_hex_prefix() {
+        let row_view = HexViewBuilder::new(&[0x4A, 0x4B])
+            .row_width(4)
+            .hex_prefix(Some("0x"))
+            .uppercase(true)
+            .show_header(true)
+            .underline(1..2)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("            00   01   02   03  | 0123 |"), lines.next());
+        assert_eq!(Some("00000000  0x4A 0x4B            | JK   |"), lines.next());
+        assert_eq!(Some("                 ^^"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn the_case_of_the_address_and_the_bytes_can_differ() {
+        let row_view = HexViewBuilder::new(&[0xAB, 0xCD])
+            .address_offset(0xEF0)
+            .row_width(2)
+            .address_uppercase(true)
+            .byte_uppercase(false)
+            .finish();
+
+        assert_eq!("00000EF0  ab cd  | ½═ |", format!("{}", row_view));
+    }
+
+    #[test]
+    fn the_address_radix_does_not_change_the_hex_panel() {
+        let data = [0x4A; 4];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(100)
+            .row_width(4)
+            .address_radix(Radix::Decimal)
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "00000100  4A 4A 4A 4A  | JJJJ |");
+    }
+
+    #[test]
+    fn decimal_addresses_can_be_grouped_by_thousands() {
+        let data = [0x41; 20];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(1_000_000)
+            .address_radix(Radix::Decimal)
+            .address_grouping(Some(','))
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert!(lines.next().unwrap().starts_with("1,000,000  41 41"));
+        assert!(lines.next().unwrap().starts_with("1,000,016  41 41"));
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn the_address_grouping_widens_the_address_column() {
+        let row_view = HexViewBuilder::new(&[0x41; 32])
+            .address_offset(99_999_984)
+            .address_radix(Radix::Decimal)
+            .address_grouping(Some('.'))
+            .show_header(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert!(lines.next().unwrap().starts_with("             00 01"));
+        assert!(lines.next().unwrap().starts_with(" 99.999.984  41 41"));
+        assert!(lines.next().unwrap().starts_with("100.000.000  41 41"));
+    }
+
+    #[test]
+    fn the_address_suffix_follows_the_address_digits() {
+        let row_view = HexViewBuilder::new(&[0x41, 0x42])
+            .row_width(2)
+            .address_suffix(": ")
+            .show_header(true)
+            .finish();
+
+        let expected = "          00 01  | 01 |\n00000000: 41 42  | AB |";
+
+        assert_eq!(expected, format!("{}", row_view));
+    }
+
+    #[test]
+    fn addresses_can_be_split_into_segment_and_offset() {
+        let row_view = HexViewBuilder::new(&[0x41, 0x42])
+            .address_offset(0x12340)
+            .segmented_address(4)
+            .finish();
+
+        assert!(format!("{}", row_view).starts_with("1000:2340  41 42"));
+    }
+
+    #[test]
+    fn the_end_address_follows_the_last_real_byte_of_a_row() {
+        let data = [0; 20];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(16)
+            .show_end_address(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert!(lines.next().unwrap().ends_with(" | -> 00000010"));
+        assert!(lines.next().unwrap().ends_with(" | -> 00000014"));
+    }
+
+    #[test]
+    fn groups_are_separated_by_the_group_gap() {
+        let data: Vec<u8> = (0x40..0x40 + 16).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .group_size(8)
+            .group_gap(3)
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "00000000  40 41 42 43 44 45 46 47   48 49 4A 4B 4C 4D 4E 4F  | @ABCDEFGHIJKLMNO |");
+    }
+
+    #[test]
+    fn grouped_rows_stay_aligned() {
+        let data: Vec<u8> = (0x40..0x40 + 16).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(3)
+            .group_size(4)
+            .show_header(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert!(result.lines().all(|line| line.chars().count() == row_view.line_width()));
+        assert!(result.contains("03  04 05 06 07  08"));
+    }
+
+    #[test]
+    fn row_numbers_are_shown_in_front_of_the_address() {
+        let data = [0x41; 10];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .show_row_numbers(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("1  00000000  41 41 41 41  | AAAA |"), lines.next());
+        assert_eq!(Some("2  00000004  41 41 41 41  | AAAA |"), lines.next());
+        assert_eq!(Some("3  00000008  41 41        | AA   |"), lines.next());
+    }
+
+    #[test]
+    fn row_numbers_are_right_aligned_to_the_row_count() {
+        let data = [0; 10];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(1)
+            .show_row_numbers(true)
+            .show_header(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let lines: Vec<&str> = result.lines().collect();
+
+        assert!(lines[1].starts_with(" 1  00000000"));
+        assert!(lines[10].starts_with("10  00000009"));
+        assert!(lines.iter().all(|line| line.chars().count() == row_view.line_width()));
+    }
+
+    #[test]
+    fn the_row_checksum_is_shown_after_the_hex_panel() {
+        let data = [0x01, 0x03, 0x04, 0xF8, 0xF0];
+
+        let xor_view = HexViewBuilder::new(&data).row_width(4).show_row_checksum(ChecksumKind::Xor).finish();
+        let sum_view = HexViewBuilder::new(&data).row_width(4).show_row_checksum(ChecksumKind::Sum8).finish();
+        let parity_view = HexViewBuilder::new(&data).row_width(4).show_row_checksum(ChecksumKind::Parity).finish();
+
+        let expected = "00000000  01 03 04 F8  FE  | ☺♥♦▫ |\n00000004  F0           F0  | ≡    |";
+
+        assert_eq!(expected, format!("{}", xor_view));
+        assert_eq!(Some("00000000  01 03 04 F8  00  | ☺♥♦▫ |"), format!("{}", sum_view).lines().next());
+        assert_eq!(Some("00000000  01 03 04 F8   1  | ☺♥♦▫ |"), format!("{}", parity_view).lines().next());
+    }
+
+    #[test]
+    fn identical_rows_are_squeezed() {
+        let mut data = [0; 48];
+        data[40] = 1;
+
+        let row_view = HexViewBuilder::new(&data)
+            .squeeze(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert!(lines.next().unwrap().starts_with("00000000  00 00"));
+        assert_eq!(Some("*"), lines.next());
+        assert!(lines.next().unwrap().starts_with("00000020  00 00"));
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn the_squeeze_marker_can_show_the_number_of_collapsed_rows() {
+        let data = [0; 128];
+
+        let row_view = HexViewBuilder::new(&data)
+            .squeeze(true)
+            .squeeze_count(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert!(lines.next().unwrap().starts_with("00000000  00 00"));
+        assert_eq!(Some("* (repeated 7 times)"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn a_squeezed_block_resumes_at_the_next_different_row() {
+        let mut data = [0; 80];
+        data[64] = 1;
+
+        let row_view = HexViewBuilder::new(&data)
+            .squeeze(true)
+            .squeeze_count(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines().skip(1);
+
+        assert_eq!(Some("* (repeated 3 times)"), lines.next());
+        assert!(lines.next().unwrap().starts_with("00000040  01 00"));
+    }
+
+    #[test]
+    fn the_sections_can_be_separated_by_tabs() {
+        let row_view = HexViewBuilder::new(&[0x41, 0x42, 0x43])
+            .row_width(4)
+            .tab_separated(true)
+            .show_header(true)
+            .finish();
+
+        let expected = "\t00 01 02 03\t| 0123 |\n00000000\t41 42 43   \t| ABC  |";
+
+        assert_eq!(expected, format!("{}", row_view));
+    }
+
+    #[test]
+    fn the_title_is_printed_above_the_header() {
+        let row_view = HexViewBuilder::new(&[0x41])
+            .row_width(1)
+            .title("firmware.bin")
+            .show_header(true)
+            .finish();
+
+        let expected = "firmware.bin\n          00  | 0 |\n00000000  41  | A |";
+
+        assert_eq!(expected, format!("{}", row_view));
+    }
+
+    #[test]
+    fn a_right_to_left_row_ends_with_the_address() {
+        let data = [0x41, 0x42, 0x43];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(0x11)
+            .row_width(4)
+            .rtl(true)
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "| CBA  |  43 42 41     00000010");
+    }
+
+    #[test]
+    fn the_address_can_be_printed_on_every_nth_row_only() {
+        let data = [0x41; 12];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .address_every(2)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00000000  41 41 41 41  | AAAA |"), lines.next());
+        assert_eq!(Some("          41 41 41 41  | AAAA |"), lines.next());
+        assert_eq!(Some("00000008  41 41 41 41  | AAAA |"), lines.next());
+    }
+
+    #[test]
+    fn the_class_bar_marks_the_non_printable_bytes() {
+        let data = [0x41, 0x00, 0x42, 0x00, 0x43];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(6)
+            .codepage(byte_mapping::CODEPAGE_ASCII)
+            .show_class_bar(true)
+            .show_header(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("          00 01 02 03 04 05          | 012345 |"), lines.next());
+        assert_eq!(Some("00000000  41 00 42 00 43     .#.#.   | A.B.C  |"), lines.next());
+    }
+
+    #[test]
+    fn a_stride_samples_every_nth_byte() {
+        let data: Vec<u8> = (0x40..0x40 + 16).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(1)
+            .stride(4)
+            .finish();
+
+        let expected = "00000000  40  | @ |\n00000004  44  | D |\n00000008  48  | H |\n0000000C  4C  | L |";
+
+        assert_eq!(expected, format!("{}", row_view));
+    }
+
+    #[test]
+    fn a_stride_pads_the_last_row() {
+        let data: Vec<u8> = (0x40..0x40 + 10).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .stride(4)
+            .show_end_address(true)
+            .finish();
+
+        assert_eq!("00000000  40 44 48     | @DH  | -> 0000000A", format!("{}", row_view));
     }
 
-    pub fn address_offset(mut self, offset: usize) -> HexViewBuilder<'a> {
-        self.hex_view.address_offset = offset;
-        self
-    }
+    #[test]
+    fn a_limit_omits_the_remaining_bytes_with_a_note() {
+        let data = [0x41; 100];
 
-    pub fn codepage<'b: 'a>(mut self, codepage: &'b [char]) -> HexViewBuilder<'a> {
-        self.hex_view.codepage = codepage;
-        self
-    }
+        let row_view = HexViewBuilder::new(&data)
+            .limit(16)
+            .finish();
 
-    pub fn row_width(mut self, width: usize) -> HexViewBuilder<'a> {
-        self.hex_view.row_width = width;
-        self
-    }
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
 
-    pub fn finish(self) -> HexView<'a> {
-        self.hex_view
+        assert_eq!(Some("00000000  41 41 41 41 41 41 41 41 41 41 41 41 41 41 41 41  | AAAAAAAAAAAAAAAA |"), lines.next());
+        assert_eq!(Some("... (+84 bytes)"), lines.next());
+        assert_eq!(None, lines.next());
     }
-}
 
-#[derive(Default)]
-struct Padding {
-    left: usize,
-    right: usize,
-}
+    #[test]
+    fn the_omit_message_can_be_customized() {
+        let data = [0x41; 100];
 
-impl Padding {
-    fn new(left_padding: usize, right_padding: usize) -> Padding {
-        Padding {
-            left: left_padding,
-            right: right_padding,
-        }
-    }
+        let row_view = HexViewBuilder::new(&data)
+            .limit(16)
+            .omit_message(|omitted| format!("SNIP:{}", omitted))
+            .finish();
 
-    fn from_left(left_padding: usize) -> Padding {
-        Padding {
-            left: left_padding,
-            right: 0,
-        }
+        assert_eq!(Some("SNIP:84"), format!("{}", row_view).lines().last());
     }
 
-    fn from_right(right_padding: usize) -> Padding {
-        Padding {
-            left: 0,
-            right: right_padding,
-        }
-    }
-}
+    #[test]
+    fn a_limit_beyond_the_data_has_no_effect() {
+        let data = [0x41; 10];
 
-fn fmt_bytes_as_hex(f: &mut Formatter, bytes: &[u8], padding: &Padding) -> Result {
-    let mut separator = "";
+        let limited = HexViewBuilder::new(&data).limit(16).finish();
+        let unlimited = HexViewBuilder::new(&data).finish();
 
-    for _ in 0..padding.left {
-        write!(f, "{}  ", separator)?;
-        separator = " ";
+        assert_eq!(format!("{}", limited), format!("{}", unlimited));
     }
 
-    for byte in bytes.iter() {
-        write!(f, "{}{:02X}", separator, byte)?;
-        separator = " ";
-    }
+    #[test]
+    fn rows_can_be_made_of_a_number_of_elements() {
+        let data: Vec<u8> = (0x40..0x40 + 12).collect();
 
-    for _ in 0..padding.right {
-        write!(f, "{}  ", separator)?;
-        separator = " ";
+        let row_view = HexViewBuilder::new(&data)
+            .element_size(4)
+            .elements_per_row(2)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00000000  40 41 42 43  44 45 46 47  | @ABCDEFG |"), lines.next());
+        assert_eq!(Some("00000008  48 49 4A 4B               | HIJK     |"), lines.next());
+        assert_eq!(None, lines.next());
     }
 
-    Ok(())
-}
+    #[test]
+    fn the_panel_divider_can_be_changed() {
+        let data = [0x41, 0x42];
 
-fn fmt_bytes_as_char(f: &mut Formatter, cp: &[char], bytes: &[u8], padding: &Padding) -> Result {
-    for _ in 0..padding.left {
-        write!(f, " ")?;
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(2)
+            .panel_divider("[ ")
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "00000000  41 42  [ AB [");
     }
 
-    for &byte in bytes.iter() {
-        write!(f, "{}", byte_mapping::as_char(byte, cp))?;
+    #[test]
+    fn an_empty_panel_divider_separates_the_panels_by_spaces_only() {
+        let data: Vec<u8> = (0x41..0x41 + 20).collect();
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(2)
+            .panel_divider("")
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00000000        41 42 43 44 45 46 47 48 49 4A 4B 4C 4D 4E    ABCDEFGHIJKLMN"), lines.next());
+        assert_eq!(Some("00000010  4F 50 51 52 53 54                                OPQRST          "), lines.next());
     }
 
-    for _ in 0..padding.right {
-        write!(f, " ")?;
+    #[test]
+    fn a_highlight_colors_the_bytes_in_its_range() {
+        let data = [0x41, 0x42, 0x43, 0x44];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .use_color(true)
+            .highlight(1..3, Color::Red)
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "00000000  41 \x1b[31m42\x1b[0m \x1b[31m43\x1b[0m 44  | A\x1b[31mB\x1b[0m\x1b[31mC\x1b[0mD |");
     }
 
-    Ok(())
-}
+    #[test]
+    fn palette_and_true_colors_can_be_highlighted() {
+        let data = [0x41, 0x42];
 
-fn fmt_line(f: &mut Formatter, address: usize, cp: &[char], bytes: &[u8], padding: &Padding) -> Result {
-    write!(f, "{:0width$X}", address, width = 8)?;
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(2)
+            .use_color(true)
+            .highlight(0..1, Color::Rgb(255, 0, 0))
+            .highlight(1..2, Color::Ansi256(208))
+            .finish();
 
-    write!(f, "  ")?;
-    fmt_bytes_as_hex(f, bytes, &padding)?;
-    write!(f, "  ")?;
+        assert_eq!(format!("{}", row_view), "00000000  \x1b[38;2;255;0;0m41\x1b[0m \x1b[38;5;208m42\x1b[0m  | \x1b[38;2;255;0;0mA\x1b[0m\x1b[38;5;208mB\x1b[0m |");
+    }
 
-    write!(f, "| ")?;
-    fmt_bytes_as_char(f, cp, bytes, &padding)?;
-    write!(f, " |")?;
+    #[test]
+    fn a_highlight_can_set_only_the_background() {
+        let data = [0x41, 0x42];
 
-    Ok(())
-}
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(2)
+            .use_color(true)
+            .highlight_styled(Highlight { range: 1..2, fg: None, bg: Some(Color::Blue) })
+            .highlight_styled(Highlight { range: 0..1, fg: Some(Color::White), bg: Some(Color::Ansi256(22)) })
+            .finish();
 
-fn calculate_begin_padding(address_offset: usize, row_width: usize) -> usize {
-    debug_assert!(row_width != 0, "A zero row width is can not be used to calculate the begin padding");
-    address_offset % row_width
-}
+        assert_eq!(format!("{}", row_view), "00000000  \x1b[37;48;5;22m41\x1b[0m \x1b[44m42\x1b[0m  | \x1b[37;48;5;22mA\x1b[0m\x1b[44mB\x1b[0m |");
+    }
 
-fn calculate_end_padding(data_size: usize, row_width: usize) -> usize {
-    debug_assert!(row_width != 0, "A zero row width is can not be used to calculate the end padding");
-    (row_width - data_size % row_width) % row_width
-}
+    #[test]
+    fn inclusive_ranges_and_lengths_can_be_highlighted() {
+        let data = [0; 8];
 
-impl<'a> std::fmt::Display for HexView<'a> {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        if self.row_width == 0 {
-            write!(f, "Invalid HexView::width")?;
-            return Err(std::fmt::Error);
-        }
+        let by_length = HexViewBuilder::new(&data).use_color(true).highlight_len(4, 3, Color::Green).finish();
+        let by_inclusive_range = HexViewBuilder::new(&data).use_color(true).highlight(4..=6, Color::Green).finish();
+        let by_range = HexViewBuilder::new(&data).use_color(true).highlight(4..7, Color::Green).finish();
 
-        let begin_padding = calculate_begin_padding(self.address_offset, self.row_width);
-        let end_padding = calculate_end_padding(begin_padding + self.data.len(), self.row_width);
-        let mut address = self.address_offset - begin_padding;
-        let mut offset = 0;
-        let mut separator = "";
+        let result = format!("{}", by_length);
 
-        if self.data.len() + begin_padding + end_padding <= self.row_width {
-            return fmt_line(f, address, &self.codepage, &self.data, &Padding::new(begin_padding, end_padding));
-        }
+        assert_eq!(result, format!("{}", by_inclusive_range));
+        assert_eq!(result, format!("{}", by_range));
+        assert!(result.starts_with("00000000  00 00 00 00 \x1b[32m00\x1b[0m \x1b[32m00\x1b[0m \x1b[32m00\x1b[0m 00"));
+    }
 
-        if begin_padding != 0 {
-            let slice = &self.data[offset..offset + self.row_width - begin_padding];
-            fmt_line(f, address, &self.codepage, &slice, &Padding::from_left(begin_padding))?;
-            offset += self.row_width - begin_padding;
-            address += self.row_width;
-            separator = "\n";
-        }
+    #[test]
+    fn highlights_up_to_the_last_address_do_not_overflow() {
+        let data = [0x41, 0x42];
 
+        let by_inclusive_range = HexViewBuilder::new(&data).use_color(true).highlight(1..=usize::MAX, Color::Red).finish();
+        let by_length = HexViewBuilder::new(&data).use_color(true).highlight_len(1, usize::MAX, Color::Red).finish();
+        let by_range = HexViewBuilder::new(&data).use_color(true).highlight(1.., Color::Red).finish();
 
-        while offset + (self.row_width - 1) < self.data.len() {
-            let slice = &self.data[offset..offset + self.row_width];
-            write!(f, "{}", separator)?;
-            fmt_line(f, address, &self.codepage, &slice, &Padding::default())?;
-            offset += self.row_width;
-            address += self.row_width;
-            separator = "\n";
-        }
+        assert_eq!(format!("{}", by_range), format!("{}", by_inclusive_range));
+        assert_eq!(format!("{}", by_range), format!("{}", by_length));
+        assert_eq!(format!("{}", HexViewBuilder::new(&data).use_color(true).highlight(..=usize::MAX, Color::Red).finish()),
+                   format!("{}", HexViewBuilder::new(&data).use_color(true).highlight(.., Color::Red).finish()));
+    }
 
-        if end_padding != 0 {
-            let slice = &self.data[offset..];
-            write!(f, "{}", separator)?;
-            fmt_line(f, address, &self.codepage, &slice, &Padding::from_right(end_padding))?;
-        }
+    #[test]
+    fn the_hexdump_compat_mode_matches_hexdump_c() {
+        let data = b"Hello, world!\n\x00\x01\x02";
 
-        Ok(())
+        let row_view = HexViewBuilder::new(data)
+            .hexdump_compat()
+            .finish();
+
+        let expected = "\
+00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 01  |Hello, world!...|
+00000010  02                                                |.|
+00000011";
+
+        assert_eq!(format!("{}", row_view), expected);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn the_hexdump_compat_mode_prints_nothing_for_empty_data() {
+        let row_view = HexViewBuilder::new(&[])
+            .hexdump_compat()
+            .finish();
+
+        assert_eq!(format!("{}", row_view), "");
+    }
 
     #[test]
-    fn test_begin_padding() {
-        // Rust 1.13 needs the fully qualified name here
-        assert_eq!(super::calculate_begin_padding(0, 16), 0);
-        assert_eq!(super::calculate_begin_padding(16, 16), 0);
-        assert_eq!(super::calculate_begin_padding(54, 16), 6);
+    fn the_hexdump_compat_mode_squeezes_duplicate_rows() {
+        let mut data = [0; 64];
+        data[48] = 0x7F;
+
+        let row_view = HexViewBuilder::new(&data)
+            .hexdump_compat()
+            .finish();
+
+        let expected = "\
+00000000  00 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  |................|
+*
+00000030  7f 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  |................|
+00000040";
+
+        assert_eq!(format!("{}", row_view), expected);
     }
 
     #[test]
-    fn a_full_line_is_formatted_as_expected() {
-        let data: Vec<u8> = (0x40..0x40 + 0xF + 1).collect();
+    fn the_pcap_style_uses_four_groups_and_a_colon_after_the_address() {
+        let data = [
+            0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0x5e, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x08, 0x00, 0x45, 0x00,
+            0x00, 0x54,
+        ];
 
         let row_view = HexViewBuilder::new(&data)
-            .row_width(data.len())
+            .pcap_style()
             .finish();
 
         let result = format!("{}", row_view);
+        let mut lines = result.lines();
 
-        assert_eq!(result, "00000000  40 41 42 43 44 45 46 47 48 49 4A 4B 4C 4D 4E 4F  | @ABCDEFGHIJKLMNO |");
+        assert_eq!(Some("00000000: 00 1a 2b 3c  4d 5e 00 11  22 33 44 55  08 00 45 00  ..+<M^..\"3DU..E."), lines.next());
+        assert_eq!(Some("00000010: 00 54                                               .T"), lines.next());
     }
 
     #[test]
-    fn an_incomplete_line_is_padded_on_the_right() {
-        let data = ['a' as u8; 10];
+    fn the_output_len_equals_the_formatted_length() {
+        let data: Vec<u8> = (0u16..300).map(|v| v as u8).collect();
+
+        let views = [
+            HexViewBuilder::new(&data).finish(),
+            HexViewBuilder::new(&data[..5]).address_offset(3).finish(),
+            HexViewBuilder::new(&data).address_offset(13).row_width(7).show_header(true).finish(),
+            HexViewBuilder::new(&data).use_color(true).highlight(4..40, Color::Red).tag(2, 'a').finish(),
+            HexViewBuilder::new(&[0; 64]).hexdump_compat().finish(),
+            HexViewBuilder::new(&data).row_width(0).finish(),
+        ];
+
+        for view in views.iter() {
+            assert_eq!(format!("{}", view).len(), view.output_len());
+        }
+    }
+
+    #[test]
+    fn underlined_bytes_are_marked_with_carets() {
+        let data: Vec<u8> = (0x40..0x40 + 8).collect();
 
         let row_view = HexViewBuilder::new(&data)
-            .row_width(16)
+            .row_width(4)
+            .underline(2..4)
             .finish();
 
         let result = format!("{}", row_view);
+        let mut lines = result.lines();
 
-        assert_eq!(result, "00000000  61 61 61 61 61 61 61 61 61 61                    | aaaaaaaaaa       |");
+        assert_eq!(Some("00000000  40 41 42 43  | @ABC |"), lines.next());
+        assert_eq!(Some("                ^^ ^^"), lines.next());
+        assert_eq!(Some("00000004  44 45 46 47  | DEFG |"), lines.next());
+        assert_eq!(None, lines.next());
     }
 
     #[test]
-    fn an_unaligned_address_causes_padded_on_the_left() {
-        let data = ['a' as u8; 11];
+    fn bytes_differing_from_the_previous_row_are_marked() {
+        let data = [1, 2, 3, 4, 1, 2, 9, 4];
 
         let row_view = HexViewBuilder::new(&data)
-            .address_offset(5)
-            .row_width(16)
+            .row_width(4)
+            .diff_against_previous(true)
             .finish();
 
         let result = format!("{}", row_view);
-        println!("{}", result);
+        let mut lines = result.lines();
 
-        assert_eq!(result, "00000000                 61 61 61 61 61 61 61 61 61 61 61  |      aaaaaaaaaaa |");
+        assert_eq!(Some("00000000  01 02 03 04  | ☺☻♥♦ |"), lines.next());
+        assert_eq!(Some("00000004  01 02 09 04  | ☺☻○♦ |"), lines.next());
+        assert_eq!(Some("                ^^"), lines.next());
+        assert_eq!(None, lines.next());
     }
 
     #[test]
-    fn an_unaligned_incomplete_line_causes_padding_on_both_sides() {
-        let data = ['a' as u8; 8];
+    fn bytes_differing_from_the_previous_row_are_colored() {
+        let data = [1, 2, 3, 4, 1, 2, 9, 4];
 
         let row_view = HexViewBuilder::new(&data)
-            .address_offset(5)
-            .row_width(16)
+            .row_width(4)
+            .diff_against_previous(true)
+            .use_color(true)
             .finish();
 
         let result = format!("{}", row_view);
-        println!("{}", result);
+        let second = result.lines().nth(1).unwrap();
 
-        assert_eq!(result, "00000000                 61 61 61 61 61 61 61 61           |      aaaaaaaa    |");
+        assert!(!result.lines().next().unwrap().contains('\x1b'));
+        assert!(second.starts_with("00000004  01 02 \x1b[31m09\x1b[0m 04"));
+        assert_eq!(2, result.lines().count());
+    }
+
+    #[test]
+    fn the_char_panel_can_be_decoded_as_utf16() {
+        let little = HexViewBuilder::new(&[0x41, 0x00, 0x42, 0x00])
+            .utf16_char_panel(Endian::Little)
+            .finish();
+        let big = HexViewBuilder::new(&[0x00, 0x41, 0x00])
+            .utf16_char_panel(Endian::Big)
+            .finish();
+        let surrogate = HexViewBuilder::new(&[0x00, 0xD8])
+            .utf16_char_panel(Endian::Little)
+            .finish();
+
+        assert!(format!("{}", little).ends_with("| A B              |"));
+        assert!(format!("{}", big).ends_with("| A \u{FFFD}              |"));
+        assert!(format!("{}", surrogate).ends_with("| \u{FFFD}                |"));
+    }
+
+    #[test]
+    fn a_gap_advances_the_addresses_of_the_appended_data() {
+        let first = [0x41, 0x42, 0x43, 0x44];
+        let second = [0x45, 0x46];
+
+        let row_view = HexViewBuilder::from_slices(&[&first])
+            .address_offset(0x10)
+            .row_width(4)
+            .gap(0x20)
+            .append(&second)
+            .finish();
+
+        let expected = "00000010  41 42 43 44  | ABCD |\n... gap of 32 bytes ...\n00000034  45 46        | EF   |";
+
+        assert_eq!(expected, format!("{}", row_view));
+    }
+
+    #[test]
+    fn a_gap_in_front_of_the_data_is_shown() {
+        let data = [0x45, 0x46];
+
+        let row_view = HexViewBuilder::from_slices(&[])
+            .address_offset(0x10)
+            .row_width(4)
+            .title("dump")
+            .gap(0x20)
+            .append(&data)
+            .finish();
+
+        let expected = "dump\n... gap of 32 bytes ...\n00000030  45 46        | EF   |";
+
+        assert_eq!(expected, format!("{}", row_view));
+    }
+
+    #[test]
+    fn rows_can_span_the_fragments_of_a_view() {
+        let first = [0x41, 0x42, 0x43];
+        let second = [0x44, 0x45, 0x46];
+
+        let row_view = HexViewBuilder::from_slices(&[&first, &second])
+            .address_offset(0x10)
+            .row_width(4)
+            .finish();
+
+        let expected = "00000010  41 42 43 44  | ABCD |\n00000014  45 46        | EF   |";
+
+        assert_eq!(expected, format!("{}", row_view));
+    }
+
+    #[test]
+    fn a_view_of_fragments_matches_the_view_of_the_joined_data() {
+        let joined = [0x41, 0x42, 0x43, 0x44, 0x45, 0x46];
+        let joined_view = HexViewBuilder::new(&joined).address_offset(0x10).finish();
+
+        let fragments_view = HexView::from_slices(&[&joined[..3], &[], &joined[3..]], 0x10);
+
+        assert_eq!(format!("{}", joined_view), format!("{}", fragments_view));
+        assert!(!fragments_view.differs_from(&joined_view));
+    }
+
+    #[test]
+    fn views_on_equal_data_do_not_differ() {
+        let data = [1, 2, 3];
+        let other = [1, 2, 3];
+
+        let view = HexViewBuilder::new(&data).finish();
+        let other_view = HexViewBuilder::new(&other).row_width(8).finish();
+
+        assert!(!view.differs_from(&other_view));
+    }
+
+    #[test]
+    fn views_on_unequal_data_differ() {
+        let data = [1, 2, 3];
+
+        let view = HexViewBuilder::new(&data).finish();
+
+        assert!(view.differs_from(&HexViewBuilder::new(&[1, 2, 4]).finish()));
+        assert!(view.differs_from(&HexViewBuilder::new(&data[..2]).finish()));
     }
 
     #[test]
@@ -319,6 +4264,39 @@ mod tests {
         assert!(result.contains(&row_4_address_offset_str));
     }
 
+    #[test]
+    fn a_row_width_of_one_shows_one_byte_per_line() {
+        let data = [0x41, 0x42, 0x43];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(1)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00000000  41  | A |"), lines.next());
+        assert_eq!(Some("00000001  42  | B |"), lines.next());
+        assert_eq!(Some("00000002  43  | C |"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn a_row_width_of_one_never_pads_an_offset_address() {
+        let data = [0x41, 0x42, 0x43];
+
+        let row_view = HexViewBuilder::new(&data)
+            .address_offset(7)
+            .row_width(1)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let addresses: Vec<&str> = result.lines().map(|line| &line[..8]).collect();
+
+        assert_eq!(vec!["00000007", "00000008", "00000009"], addresses);
+        assert!(result.lines().all(|line| line.len() == "00000000  41  | A |".len()));
+    }
+
     #[test]
     fn the_row_width_is_16_by_default() {
         let data = [0; 17];
@@ -347,4 +4325,70 @@ mod tests {
 
         assert!(!result.is_empty());
     }
+
+    #[test]
+    fn tags_are_listed_in_a_footer_ordered_by_offset() {
+        let data = [0; 16];
+
+        let row_view = HexViewBuilder::new(&data)
+            .tag(7, 'b')
+            .tag(3, 'a')
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(Some("a=offset 3, b=offset 7"), result.lines().last());
+        assert_eq!(2, result.lines().count());
+    }
+
+    #[test]
+    fn the_header_shows_the_column_indices() {
+        let data = [0; 16];
+
+        let row_view = HexViewBuilder::new(&data)
+            .show_header(true)
+            .finish();
+
+        let result = format!("{}", row_view);
+
+        assert_eq!(Some("          00 01 02 03 04 05 06 07 08 09 0A 0B 0C 0D 0E 0F  | 0123456789ABCDEF |"), result.lines().next());
+        assert_eq!(2, result.lines().count());
+    }
+
+    #[test]
+    fn header_ticks_are_placed_under_every_nth_column() {
+        let data = [0; 16];
+
+        let row_view = HexViewBuilder::new(&data)
+            .row_width(16)
+            .show_header(true)
+            .header_tick(4)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines();
+        let header = lines.next().unwrap();
+        let ticks = lines.next().unwrap();
+
+        let tick_positions: Vec<usize> = ticks.match_indices("^^").map(|(index, _)| index).collect();
+        let expected_positions: Vec<usize> = ["00", "04", "08", "0C"].iter().map(|column| header.find(column).unwrap()).collect();
+
+        assert_eq!(tick_positions, expected_positions);
+    }
+
+    #[test]
+    fn a_bitfield_shows_the_labeled_bits_of_the_selected_bytes() {
+        let data = [0xFF, 0b1010_0000, 0xFF];
+
+        let row_view = HexViewBuilder::new(&data)
+            .bitfield(1..2)
+            .finish();
+
+        let result = format!("{}", row_view);
+        let mut lines = result.lines().skip(1);
+
+        assert_eq!(Some("          7 6 5 4 3 2 1 0"), lines.next());
+        assert_eq!(Some("00000001  1 0 1 0 0 0 0 0"), lines.next());
+        assert_eq!(None, lines.next());
+    }
 }
@@ -0,0 +1,612 @@
+#[cfg(feature = "ihex")]
+use std::cmp;
+
+use byte_mapping;
+
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter, Result};
+
+use super::{Hex, HexView, Radix, RowLine};
+
+
+impl<'a> HexView<'a> {
+    /// Formats the shown data as a C array initializer named `name`.
+    ///
+    /// Every line of the initializer holds `row_width` bytes and the digits follow the
+    /// [uppercase](struct.HexViewBuilder.html#method.uppercase) setting. The address and char
+    /// panels are omitted.
+    pub fn to_c_array(&self, name: &str) -> String {
+        let data = self.shown_bytes();
+        let mut result = format!("const unsigned char {}[] = {{\n", name);
+        let row_width = if self.row_width == 0 { data.len() } else { self.row_width };
+
+        for (index, row) in data.chunks(row_width.max(1)).enumerate() {
+            let bytes: Vec<String> = row.iter()
+                .map(|&byte| format!("0x{}", Hex::new(byte as usize, 2, self.byte_uppercase)))
+                .collect();
+
+            if index != 0 {
+                result.push_str(",\n");
+            }
+
+            result.push_str("    ");
+            result.push_str(&bytes.join(", "));
+        }
+
+        if !data.is_empty() {
+            result.push('\n');
+        }
+
+        result.push_str("};");
+        result
+    }
+
+    /// Formats the shown data as one continuous string of two hex digits per byte, without
+    /// addresses, char panel or line breaks. The digits follow the
+    /// [uppercase](struct.HexViewBuilder.html#method.uppercase) setting.
+    pub fn to_hex_string(&self) -> String {
+        self.shown_bytes().iter()
+            .map(|&byte| Hex::new(byte as usize, 2, self.byte_uppercase).to_string())
+            .collect()
+    }
+
+    /// Formats the view as an HTML `pre` element, the text is escaped and the colors are omitted.
+    /// The element is empty for a zero row width.
+    ///
+    /// The title, header, squeezed rows and footers are rendered like the text output. The
+    /// [gaps](struct.HexViewBuilder.html#method.gap), [strings](struct.HexViewBuilder.html#method.strings_only),
+    /// line prefixes, indentation and line continuations are ignored.
+    pub fn to_html(&self) -> String {
+        let mut result = String::from("<pre class=\"hexplay\">\n");
+
+        if self.row_width == 0 {
+            result.push_str("</pre>");
+            return result;
+        }
+
+        let mut view = self.laid_out();
+        view.use_color = false;
+
+        let head = FnDisplay(|f: &mut Formatter| {
+            if let Some(title) = view.title {
+                writeln!(f, "{}", title)?;
+            }
+
+            view.fmt_header(f)?;
+
+            if view.cell_borders {
+                view.fmt_cell_border(f, '┬')?;
+                writeln!(f)?;
+            }
+
+            Ok(())
+        });
+        result.push_str(&escape_html(&head.to_string()));
+
+        for line in view.row_lines() {
+            match line {
+                RowLine::Row(row) => {
+                    let address = row.address;
+                    let text = FnDisplay(|f: &mut Formatter| {
+                        view.fmt_line(f, &row)?;
+                        view.fmt_signed_decimals(f, &row)?;
+                        view.fmt_underline(f, &row)
+                    });
+                    let text = escape_html(&text.to_string());
+
+                    if self.html_row_anchors {
+                        result.push_str(&format!("<span id=\"off-{:04x}\">{}</span>\n", address, text));
+                    } else {
+                        result.push_str(&text);
+                        result.push('\n');
+                    }
+                }
+                RowLine::Squeezed(squeezed) => {
+                    let marker = FnDisplay(|f: &mut Formatter| view.fmt_squeeze_marker(f, squeezed));
+                    result.push_str(&marker.to_string());
+                    result.push('\n');
+                }
+            }
+        }
+
+        let tail = FnDisplay(|f: &mut Formatter| {
+            if view.cell_borders {
+                view.fmt_cell_border(f, '┴')?;
+            }
+
+            view.fmt_footer(f)
+        });
+        let tail = escape_html(&tail.to_string());
+        let tail = tail.strip_prefix('\n').unwrap_or(&tail);
+
+        if !tail.is_empty() {
+            result.push_str(tail);
+            result.push('\n');
+        }
+
+        result.push_str("</pre>");
+        result
+    }
+
+    /// Formats the shown data as a Rust literal that can be used as a `&[u8]`.
+    ///
+    /// Data consisting of printable ASCII only is formatted as a byte string (`b"..."`), any
+    /// other data as an array of `u8` with `row_width` bytes per line.
+    pub fn to_rust_literal(&self) -> String {
+        let data = self.shown_bytes();
+
+        if data.iter().all(|&byte| byte_mapping::is_printable_ascii(byte)) {
+            let mut result = String::from("b\"");
+
+            for &byte in data.iter() {
+                if byte == b'"' || byte == b'\\' {
+                    result.push('\\');
+                }
+
+                result.push(byte as char);
+            }
+
+            result.push('"');
+            return result;
+        }
+
+        let row_width = if self.row_width == 0 { data.len() } else { self.row_width };
+        let mut result = String::from("[\n");
+
+        for (index, row) in data.chunks(row_width).enumerate() {
+            let bytes: Vec<String> = row.iter()
+                .enumerate()
+                .map(|(column, &byte)| {
+                    let suffix = if index == 0 && column == 0 { "u8" } else { "" };
+                    format!("0x{}{}", Hex::new(byte as usize, 2, self.byte_uppercase), suffix)
+                })
+                .collect();
+
+            result.push_str("    ");
+            result.push_str(&bytes.join(", "));
+            result.push_str(",\n");
+        }
+
+        result.push(']');
+        result
+    }
+
+    /// Formats the data as [Intel HEX](https://en.wikipedia.org/wiki/Intel_HEX) records, starting
+    /// at the address offset.
+    ///
+    /// The data records hold up to 16 bytes each, an extended linear address record is emitted
+    /// whenever the upper 16 bits of the address change. The output ends with an end of file
+    /// record.
+    ///
+    /// Intel HEX addresses are 32 bits wide, `None` is returned when the data extends past 4 GiB.
+    #[cfg(feature = "ihex")]
+    pub fn to_intel_hex(&self) -> Option<String> {
+        let data = self.data.joined();
+        self.end_address_32(data.len())?;
+
+        let mut result = String::new();
+        let mut upper_address = 0;
+        let mut offset = 0;
+
+        while offset < data.len() {
+            let address = self.address_offset + offset;
+
+            if address >> 16 != upper_address {
+                upper_address = address >> 16;
+                push_intel_hex_record(&mut result, 0, 0x04, &[(upper_address >> 8) as u8, upper_address as u8]);
+            }
+
+            let count = cmp::min(cmp::min(16, data.len() - offset), 0x10000 - (address & 0xFFFF));
+            push_intel_hex_record(&mut result, address & 0xFFFF, 0x00, &data[offset..offset + count]);
+            offset += count;
+        }
+
+        push_intel_hex_record(&mut result, 0, 0x01, &[]);
+        Some(result)
+    }
+
+    /// Formats the data as [Motorola S-records](https://en.wikipedia.org/wiki/SREC_(file_format)),
+    /// starting at the address offset.
+    ///
+    /// The data records hold up to 16 bytes each and use the smallest address field (S1, S2 or
+    /// S3) that fits the end address of the data. They are followed by a record count (S5 or S6)
+    /// and the matching termination record (S9, S8 or S7). The count is left out when it doesn't
+    /// fit in 24 bits.
+    ///
+    /// S-record addresses are 32 bits wide, `None` is returned when the data extends past 4 GiB.
+    pub fn to_srec(&self) -> Option<String> {
+        let data = self.data.joined();
+        let end_address = self.end_address_32(data.len())?;
+        let (data_type, termination_type, address_size) = if end_address <= 0x10000 {
+            (1, 9, 2)
+        } else if end_address <= 0x100_0000 {
+            (2, 8, 3)
+        } else {
+            (3, 7, 4)
+        };
+
+        let mut result = String::new();
+        let mut record_count = 0;
+
+        for (index, chunk) in data.chunks(16).enumerate() {
+            push_srec_record(&mut result, data_type, self.address_offset + 16 * index, address_size, chunk);
+            record_count += 1;
+        }
+
+        if record_count <= 0xFFFF {
+            push_srec_record(&mut result, 5, record_count, 2, &[]);
+        } else if record_count <= 0xFF_FFFF {
+            push_srec_record(&mut result, 6, record_count, 3, &[]);
+        }
+
+        push_srec_record(&mut result, termination_type, 0, address_size, &[]);
+        Some(result)
+    }
+
+    /// Returns the address just past `len` bytes from the address offset, if it fits in 32 bits.
+    fn end_address_32(&self, len: usize) -> Option<usize> {
+        self.address_offset.checked_add(len).filter(|&end| end as u64 <= 1 << 32)
+    }
+
+    /// Returns the bytes that are shown, up to the limit and with the stride applied.
+    fn shown_bytes(&self) -> Cow<'a, [u8]> {
+        if self.stride == 1 {
+            self.shown_data().joined()
+        } else {
+            Cow::Owned(self.shown_data().iter().step_by(self.stride).collect())
+        }
+    }
+
+    /// Formats the display rows as comma separated values, the address followed by one field per
+    /// column with the byte in hex. The padding columns are left empty.
+    pub fn to_csv(&self) -> String {
+        self.to_csv_radix(Radix::Hexadecimal)
+    }
+
+    /// Like [to_csv](#method.to_csv), with the bytes written in `radix`.
+    pub fn to_csv_radix(&self, radix: Radix) -> String {
+        let mut result = String::new();
+
+        if self.row_width == 0 {
+            return result;
+        }
+
+        for row in self.rows() {
+            result.push_str(&format!("{}", AddressDisplay { view: self, address: row.address }));
+
+            for cell in row.cells() {
+                result.push(',');
+
+                if let Some((_, byte)) = cell {
+                    result.push_str(&match radix {
+                        Radix::Binary => format!("{:08b}", byte),
+                        Radix::Octal => format!("{:03o}", byte),
+                        Radix::Decimal => format!("{}", byte),
+                        Radix::Hexadecimal => format!("{}", Hex::new(byte as usize, 2, self.byte_uppercase)),
+                    });
+                }
+            }
+
+            result.push('\n');
+        }
+
+        result
+    }
+}
+
+/// Displays an address as configured for a [HexView](struct.HexView.html).
+struct AddressDisplay<'v, 'a: 'v> {
+    view: &'v HexView<'a>,
+    address: usize,
+}
+
+impl<'v, 'a> Display for AddressDisplay<'v, 'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        self.view.fmt_address(f, self.address)
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut result = String::new();
+
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+fn push_srec_record(result: &mut String, record_type: u8, address: usize, address_size: usize, data: &[u8]) {
+    let mut record = vec![(address_size + data.len() + 1) as u8];
+    record.extend((0..address_size).rev().map(|index| (address >> (8 * index)) as u8));
+    record.extend_from_slice(data);
+
+    let sum = record.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+    record.push(!sum);
+
+    result.push_str(&format!("S{}", record_type));
+
+    for &byte in record.iter() {
+        result.push_str(&format!("{:02X}", byte));
+    }
+
+    result.push('\n');
+}
+
+/// Displays what the closure writes.
+struct FnDisplay<F: Fn(&mut Formatter) -> Result>(F);
+
+impl<F: Fn(&mut Formatter) -> Result> Display for FnDisplay<F> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        (self.0)(f)
+    }
+}
+
+#[cfg(feature = "ihex")]
+fn push_intel_hex_record(result: &mut String, address: usize, record_type: u8, data: &[u8]) {
+    let mut record = vec![data.len() as u8, (address >> 8) as u8, address as u8, record_type];
+    record.extend_from_slice(data);
+
+    let sum = record.iter().fold(0u8, |sum, &byte| sum.wrapping_add(byte));
+    record.push(sum.wrapping_neg());
+
+    result.push(':');
+
+    for &byte in record.iter() {
+        result.push_str(&format!("{:02X}", byte));
+    }
+
+    result.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use format::{HexViewBuilder, Radix};
+
+    use super::escape_html;
+
+    #[test]
+    fn the_data_is_exported_as_a_c_array() {
+        let data: Vec<u8> = (0x40..0x40 + 10).collect();
+
+        let view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .finish();
+
+        let result = view.to_c_array("blob");
+        let mut lines = result.lines();
+
+        assert_eq!(Some("const unsigned char blob[] = {"), lines.next());
+        assert_eq!(Some("    0x40, 0x41, 0x42, 0x43,"), lines.next());
+        assert_eq!(Some("    0x44, 0x45, 0x46, 0x47,"), lines.next());
+        assert_eq!(Some("    0x48, 0x49"), lines.next());
+        assert_eq!(Some("};"), lines.next());
+        assert_eq!(10, result.matches("0x").count());
+    }
+
+    #[test]
+    fn the_c_array_holds_the_shown_bytes() {
+        let data: Vec<u8> = (0x40..0x40 + 10).collect();
+
+        let limited = HexViewBuilder::new(&data).limit(3).finish();
+        let strided = HexViewBuilder::new(&data).limit(7).stride(3).finish();
+
+        assert_eq!("const unsigned char blob[] = {\n    0x40, 0x41, 0x42\n};", limited.to_c_array("blob"));
+        assert_eq!("const unsigned char blob[] = {\n    0x40, 0x43, 0x46\n};", strided.to_c_array("blob"));
+    }
+
+    #[test]
+    fn the_c_array_follows_the_hex_case() {
+        let data = [0x4A];
+
+        let view = HexViewBuilder::new(&data)
+            .uppercase(false)
+            .finish();
+
+        assert_eq!("const unsigned char blob[] = {\n    0x4a\n};", view.to_c_array("blob"));
+    }
+
+    #[test]
+    #[cfg(feature = "ihex")]
+    fn the_data_is_exported_as_intel_hex_records() {
+        let data = [0x01, 0x02, 0x03];
+
+        let view = HexViewBuilder::new(&data)
+            .address_offset(0x0100)
+            .finish();
+
+        assert_eq!(Some(":03010000010203F6\n:00000001FF\n".to_string()), view.to_intel_hex());
+    }
+
+    #[test]
+    #[cfg(feature = "ihex")]
+    fn intel_hex_uses_extended_linear_addresses_beyond_64k() {
+        let data = [0xAA; 4];
+
+        let view = HexViewBuilder::new(&data)
+            .address_offset(0xFFFE)
+            .finish();
+
+        let result = view.to_intel_hex().unwrap();
+        let mut lines = result.lines();
+
+        assert_eq!(Some(":02FFFE00AAAAAD"), lines.next());
+        assert_eq!(Some(":020000040001F9"), lines.next());
+        assert_eq!(Some(":02000000AAAAAA"), lines.next());
+        assert_eq!(Some(":00000001FF"), lines.next());
+    }
+
+    #[test]
+    #[cfg(all(feature = "ihex", target_pointer_width = "64"))]
+    fn intel_hex_rejects_addresses_beyond_4g() {
+        let data = [0xAA; 4];
+
+        let last = HexViewBuilder::new(&data).address_offset(0xFFFF_FFFC).finish();
+        let beyond = HexViewBuilder::new(&data).address_offset(0xFFFF_FFFD).finish();
+
+        assert!(last.to_intel_hex().is_some());
+        assert_eq!(None, beyond.to_intel_hex());
+    }
+
+    #[test]
+    fn the_data_is_exported_as_s_records() {
+        let data = [0x01, 0x02, 0x03];
+
+        let view = HexViewBuilder::new(&data)
+            .address_offset(0x0100)
+            .finish();
+
+        assert_eq!(Some("S1060100010203F2\nS5030001FB\nS9030000FC\n".to_string()), view.to_srec());
+    }
+
+    #[test]
+    fn s_records_widen_the_address_field_for_high_addresses() {
+        let data = [0xAA];
+
+        let s2_view = HexViewBuilder::new(&data).address_offset(0x12_3456).finish();
+        let s3_view = HexViewBuilder::new(&data).address_offset(0x1234_5678).finish();
+
+        let s2_records = s2_view.to_srec().unwrap();
+        let s3_records = s3_view.to_srec().unwrap();
+
+        assert_eq!(Some("S205123456AAB4"), s2_records.lines().next());
+        assert_eq!(Some("S804000000FB"), s2_records.lines().last());
+        assert_eq!(Some("S30612345678AA3B"), s3_records.lines().next());
+        assert_eq!(Some("S70500000000FA"), s3_records.lines().last());
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn s_records_reject_addresses_beyond_4g() {
+        let data = [0xAA; 4];
+
+        let last = HexViewBuilder::new(&data).address_offset(0xFFFF_FFFC).finish();
+        let beyond = HexViewBuilder::new(&data).address_offset(0xFFFF_FFFD).finish();
+        let overflowing = HexViewBuilder::new(&data).address_offset(usize::MAX).finish();
+
+        assert_eq!(Some("S70500000000FA"), last.to_srec().unwrap().lines().last());
+        assert_eq!(None, beyond.to_srec());
+        assert_eq!(None, overflowing.to_srec());
+    }
+
+    #[test]
+    fn the_data_is_exported_as_a_hex_string() {
+        let data = [0xDE, 0xAD];
+
+        assert_eq!("DEAD", HexViewBuilder::new(&data).finish().to_hex_string());
+        assert_eq!("dead", HexViewBuilder::new(&data).uppercase(false).finish().to_hex_string());
+    }
+
+    #[test]
+    fn the_hex_string_holds_the_shown_bytes() {
+        let data = [0xDE, 0xAD, 0xBE, 0xEF];
+
+        assert_eq!("DEAD", HexViewBuilder::new(&data).limit(2).finish().to_hex_string());
+        assert_eq!("DEBE", HexViewBuilder::new(&data).stride(2).finish().to_hex_string());
+    }
+
+    #[test]
+    fn the_rows_are_exported_as_escaped_html() {
+        let data = b"<a>";
+
+        let view = HexViewBuilder::new(data)
+            .row_width(4)
+            .finish();
+
+        assert_eq!("<pre class=\"hexplay\">\n00000000  3C 61 3E     | &lt;a&gt;  |\n</pre>", view.to_html());
+    }
+
+    #[test]
+    fn the_html_holds_the_whole_text_output() {
+        let data = [b'&'; 72];
+
+        let view = HexViewBuilder::new(&data)
+            .title("<dump>")
+            .show_header(true)
+            .squeeze(true)
+            .limit(68)
+            .finish();
+
+        let expected = format!("<pre class=\"hexplay\">\n{}\n</pre>", escape_html(&view.to_string()));
+
+        assert_eq!(expected, view.to_html());
+        assert!(expected.contains("\n*\n"));
+        assert!(expected.contains("&lt;dump&gt;"));
+    }
+
+    #[test]
+    fn a_zero_row_width_exports_an_empty_html_element() {
+        let data = [0x41];
+
+        let view = HexViewBuilder::new(&data)
+            .row_width(0)
+            .finish();
+
+        assert_eq!("<pre class=\"hexplay\">\n</pre>", view.to_html());
+    }
+
+    #[test]
+    fn the_html_rows_can_carry_anchors() {
+        let data = [b'a'; 20];
+
+        let view = HexViewBuilder::new(&data)
+            .row_width(16)
+            .html_row_anchors(true)
+            .finish();
+
+        let result = view.to_html();
+        let mut lines = result.lines().skip(1);
+
+        assert!(lines.next().unwrap().starts_with("<span id=\"off-0000\">00000000  61"));
+        assert!(lines.next().unwrap().starts_with("<span id=\"off-0010\">00000010  61"));
+    }
+
+    #[test]
+    fn printable_data_is_exported_as_a_byte_string() {
+        let data = b"say \"hi\" \\o/";
+
+        let view = HexViewBuilder::new(data).finish();
+
+        assert_eq!("b\"say \\\"hi\\\" \\\\o/\"", view.to_rust_literal());
+    }
+
+    #[test]
+    fn the_rust_literal_holds_the_shown_bytes() {
+        let data = [0x41, 0x42, 0x43, 0x00, 0x44];
+
+        let limited = HexViewBuilder::new(&data).limit(3).finish();
+        let strided = HexViewBuilder::new(&data).stride(2).finish();
+
+        assert_eq!("b\"ABC\"", limited.to_rust_literal());
+        assert_eq!("b\"ACD\"", strided.to_rust_literal());
+    }
+
+    #[test]
+    fn binary_data_is_exported_as_a_numeric_array() {
+        let data = [0x40, 0x41, 0x00, 0xFF, 0x7F];
+
+        let view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .finish();
+
+        assert_eq!("[\n    0x40u8, 0x41, 0x00, 0xFF,\n    0x7F,\n]", view.to_rust_literal());
+    }
+
+    #[test]
+    fn the_rows_are_exported_as_csv() {
+        let data: Vec<u8> = (0x40..0x40 + 5).collect();
+
+        let view = HexViewBuilder::new(&data)
+            .row_width(4)
+            .finish();
+
+        assert_eq!("00000000,40,41,42,43\n00000004,44,,,\n", view.to_csv());
+        assert_eq!("00000000,64,65,66,67\n00000004,68,,,\n", view.to_csv_radix(Radix::Decimal));
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt::{Display, Formatter, Result};
+
+use color::{self, Color};
+
+use super::{HexView, HexViewBuilder};
+
+
+/// The colors given to the fields, in order of their declaration.
+const FIELD_COLORS: [Color; 6] = [Color::Red, Color::Green, Color::Yellow, Color::Blue, Color::Magenta, Color::Cyan];
+
+/// A [HexView](struct.HexView.html) of a struct-like layout, where the bytes of every field are
+/// colored distinctly and a legend below the dump maps the colors to the field names.
+pub struct FieldView<'a> {
+    view: HexView<'a>,
+    fields: Vec<(&'a str, usize, usize, Color)>,
+}
+
+impl<'a> FieldView<'a> {
+    /// Creates a view from the configured `builder` and the `(name, offset, len)` descriptors of
+    /// the fields, the offsets are relative to the start of the data.
+    pub fn new(builder: HexViewBuilder<'a>, fields: &[(&'a str, usize, usize)]) -> FieldView<'a> {
+        let fields: Vec<_> = fields.iter()
+            .zip(FIELD_COLORS.iter().cycle())
+            .map(|(&(name, offset, len), &color)| (name, offset, len, color))
+            .collect();
+
+        let builder = fields.iter().fold(builder.use_color(true), |builder, &(_, offset, len, color)| {
+            builder.highlight_len(offset, len, color)
+        });
+
+        FieldView {
+            view: builder.finish(),
+            fields,
+        }
+    }
+}
+
+impl<'a> Display for FieldView<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self.view)?;
+
+        let address_offset = self.view.shown_address_offset();
+
+        for &(name, offset, len, color) in self.fields.iter() {
+            let start = address_offset.saturating_add(offset);
+
+            writeln!(f)?;
+            self.view.fmt_address(f, start)?;
+            write!(f, "..")?;
+            self.view.fmt_address(f, start.saturating_add(len))?;
+            write!(f, "  ")?;
+            color::fmt_colored(f, Some(color), name)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use format::HexViewBuilder;
+
+    use super::*;
+
+    #[test]
+    fn the_fields_are_colored_and_listed_in_a_legend() {
+        let data = [0x01, 0x02, 0x03, 0x04];
+
+        let field_view = FieldView::new(HexViewBuilder::new(&data), &[("kind", 0, 1), ("length", 1, 2)]);
+
+        let result = format!("{}", field_view);
+        let mut lines = result.lines();
+
+        assert!(lines.next().unwrap().starts_with("00000000  \x1b[31m01\x1b[0m \x1b[32m02\x1b[0m \x1b[32m03\x1b[0m 04"));
+        assert_eq!(Some("00000000..00000001  \x1b[31mkind\x1b[0m"), lines.next());
+        assert_eq!(Some("00000001..00000003  \x1b[32mlength\x1b[0m"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn a_field_up_to_the_last_address_does_not_overflow() {
+        let data = [0xAA; 4];
+
+        let field_view = FieldView::new(HexViewBuilder::new(&data).address_offset(0x10), &[("tail", 2, usize::MAX)]);
+
+        let result = format!("{}", field_view);
+
+        assert_eq!(Some(format!("00000012..{:08X}  \x1b[31mtail\x1b[0m", usize::MAX).as_str()), result.lines().last());
+    }
+
+    #[test]
+    fn the_legend_addresses_are_formatted_like_the_rows() {
+        let data = [0xAA; 4];
+
+        let builder = HexViewBuilder::new(&data).address_offset(0xFE).uppercase(false);
+        let field_view = FieldView::new(builder, &[("kind", 0, 4)]);
+
+        let result = format!("{}", field_view);
+        let mut lines = result.lines();
+
+        assert!(lines.next().unwrap().starts_with("000000f0  "));
+        assert_eq!(Some("000000fe..00000102  \x1b[31mkind\x1b[0m"), lines.nth(1));
+    }
+}
@@ -0,0 +1,125 @@
+use std::borrow::Cow;
+use std::cmp;
+use std::ops::Range;
+
+
+/// The data of a view, made of one or more fragments that form one logical stream of bytes.
+#[derive(Clone)]
+pub struct Fragments<'a> {
+    fragments: Vec<&'a [u8]>,
+    len: usize,
+}
+
+impl<'a> Fragments<'a> {
+    pub fn new(fragments: &[&'a [u8]]) -> Fragments<'a> {
+        Fragments {
+            fragments: fragments.to_vec(),
+            len: fragments.iter().map(|fragment| fragment.len()).sum(),
+        }
+    }
+
+    pub fn push(&mut self, fragment: &'a [u8]) {
+        self.fragments.push(fragment);
+        self.len += fragment.len();
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, mut offset: usize) -> Option<u8> {
+        for fragment in self.fragments.iter() {
+            if offset < fragment.len() {
+                return Some(fragment[offset]);
+            }
+
+            offset -= fragment.len();
+        }
+
+        None
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        self.fragments.iter().flat_map(|fragment| fragment.iter().cloned())
+    }
+
+    /// Returns the bytes in `range`, they are only copied when the range spans several fragments.
+    pub fn slice(&self, range: Range<usize>) -> Cow<'a, [u8]> {
+        let mut bytes = Vec::new();
+        let mut start = 0;
+
+        for &fragment in self.fragments.iter() {
+            let end = start + fragment.len();
+
+            if range.start >= start && range.end <= end {
+                return Cow::Borrowed(&fragment[range.start - start..range.end - start]);
+            }
+
+            if range.start < end && range.end > start {
+                bytes.extend_from_slice(&fragment[cmp::max(range.start, start) - start..cmp::min(range.end, end) - start]);
+            }
+
+            start = end;
+        }
+
+        Cow::Owned(bytes)
+    }
+
+    /// Returns all bytes, they are only copied when there are several fragments.
+    pub fn joined(&self) -> Cow<'a, [u8]> {
+        self.slice(0..self.len)
+    }
+
+    /// Returns the first `len` bytes.
+    pub fn prefix(&self, len: usize) -> Fragments<'a> {
+        self.range(0..len)
+    }
+
+    /// Returns the bytes in `range` without copying them, the range is clamped to the data.
+    pub fn range(&self, range: Range<usize>) -> Fragments<'a> {
+        let mut fragments = Vec::new();
+        let mut start = 0;
+
+        for &fragment in self.fragments.iter() {
+            let end = start + fragment.len();
+
+            if range.start < end && range.end > start {
+                fragments.push(&fragment[cmp::max(range.start, start) - start..cmp::min(range.end, end) - start]);
+            }
+
+            start = end;
+        }
+
+        Fragments::new(&fragments)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_slice_within_a_fragment_is_borrowed() {
+        let fragments = Fragments::new(&[&[1, 2, 3], &[4, 5, 6]]);
+
+        match fragments.slice(3..5) {
+            Cow::Borrowed(bytes) => assert_eq!(bytes, &[4, 5]),
+            Cow::Owned(_) => panic!("The slice was copied"),
+        }
+    }
+
+    #[test]
+    fn a_slice_spanning_fragments_is_joined() {
+        let fragments = Fragments::new(&[&[1, 2, 3], &[], &[4, 5, 6]]);
+
+        assert_eq!(&fragments.slice(1..5)[..], &[2, 3, 4, 5]);
+        assert_eq!(Some(4), fragments.get(3));
+        assert_eq!(None, fragments.get(6));
+        assert_eq!(vec![1, 2, 3, 4], fragments.prefix(4).iter().collect::<Vec<_>>());
+        assert_eq!(vec![3, 4, 5], fragments.range(2..5).iter().collect::<Vec<_>>());
+    }
+}
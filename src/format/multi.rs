@@ -0,0 +1,121 @@
+use std::cmp;
+use std::fmt::{Display, Formatter, Result};
+
+use super::HexView;
+
+
+/// Displays several regions of memory, e.g. the sections of a binary, one after another.
+///
+/// All regions share one address column that is wide enough for the largest address of any
+/// region, so the rows of all regions align.
+///
+/// The regions are expected in ascending order of their addresses. A hole between two regions,
+/// e.g. an unmapped range of a memory map, is shown as a `... gap of N bytes ...` line, adjacent
+/// regions are separated by an empty line.
+pub struct MultiHexView<'a> {
+    regions: Vec<HexView<'a>>,
+}
+
+impl<'a> MultiHexView<'a> {
+    pub fn new(mut regions: Vec<HexView<'a>>) -> MultiHexView<'a> {
+        let address_width = regions.iter().map(|region| region.address_width()).max().unwrap_or(0);
+
+        for region in regions.iter_mut() {
+            region.min_address_width = cmp::max(region.min_address_width, address_width);
+        }
+
+        MultiHexView { regions }
+    }
+}
+
+impl<'a> Display for MultiHexView<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let mut end_address = None;
+
+        for region in self.regions.iter() {
+            match end_address {
+                Some(end_address) if region.address_offset > end_address => {
+                    write!(f, "\n... gap of {} bytes ...\n", region.address_offset - end_address)?
+                }
+                Some(_) => write!(f, "\n\n")?,
+                None => {}
+            }
+
+            write!(f, "{}", region)?;
+            end_address = Some(region.address_offset + region.data.len());
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use format::{HexViewBuilder, Radix};
+
+    use super::*;
+
+    #[test]
+    fn all_regions_share_the_widest_address_column() {
+        let data = [0x41; 4];
+
+        let region = |offset| {
+            HexViewBuilder::new(&data)
+                .address_offset(offset)
+                .row_width(4)
+                .address_radix(Radix::Decimal)
+                .address_grouping(Some(','))
+                .finish()
+        };
+        let multi_view = MultiHexView::new(vec![region(0x10), region(0x100000)]);
+
+        let expected = "       16  41 41 41 41  | AAAA |\n... gap of 1048556 bytes ...\n1,048,576  41 41 41 41  | AAAA |";
+
+        assert_eq!(expected, format!("{}", multi_view));
+    }
+
+    #[test]
+    fn adjacent_regions_are_separated_by_an_empty_line() {
+        let multi_view = MultiHexView::new(vec![
+            HexViewBuilder::new(&[0x41, 0x42]).row_width(2).finish(),
+            HexViewBuilder::new(&[0x43, 0x44]).address_offset(2).row_width(2).finish(),
+        ]);
+
+        let expected = "00000000  41 42  | AB |\n\n00000002  43 44  | CD |";
+
+        assert_eq!(expected, format!("{}", multi_view));
+    }
+
+    #[test]
+    fn a_hole_between_regions_is_shown_as_a_gap() {
+        let data: Vec<u8> = (0x41..0x41 + 6).collect();
+
+        let multi_view = MultiHexView::new(vec![
+            HexViewBuilder::new(&data[..3]).address_offset(0x10).row_width(4).finish(),
+            HexViewBuilder::new(&data[3..]).address_offset(0x20).row_width(4).finish(),
+        ]);
+
+        let result = format!("{}", multi_view);
+        let mut lines = result.lines();
+
+        assert_eq!(Some("00000010  41 42 43     | ABC  |"), lines.next());
+        assert_eq!(Some("... gap of 13 bytes ..."), lines.next());
+        assert_eq!(Some("00000020  44 45 46     | DEF  |"), lines.next());
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn large_hex_addresses_widen_the_column_of_every_region() {
+        let data = [0x41; 4];
+
+        let multi_view = MultiHexView::new(vec![
+            HexViewBuilder::new(&data).address_offset(0x10).row_width(4).finish(),
+            HexViewBuilder::new(&data).address_offset(0x100000000).row_width(4).finish(),
+        ]);
+
+        let expected = "000000010  41 41 41 41  | AAAA |\n... gap of 4294967276 bytes ...\n100000000  41 41 41 41  | AAAA |";
+
+        assert_eq!(expected, format!("{}", multi_view));
+    }
+}
@@ -0,0 +1,74 @@
+use std::fmt::{Display, Formatter, Result};
+
+use super::{Fragments, HexView, HexViewBuilder};
+
+
+/// A [HexView](struct.HexView.html) that owns its data, so it can be returned from a function
+/// without tying it to the lifetime of a buffer.
+///
+/// The rendering is shared with the borrowing `HexView`.
+#[derive(Clone)]
+pub struct OwnedHexView {
+    data: Vec<u8>,
+    config: HexView<'static>,
+}
+
+impl OwnedHexView {
+    /// Creates a view of `data` with the default configuration.
+    pub fn new(data: Vec<u8>) -> OwnedHexView {
+        OwnedHexView::with_config(data, HexViewBuilder::new(&[]))
+    }
+
+    /// Creates a view of `data` with the configuration of `builder`, the data of the builder is
+    /// ignored.
+    pub fn with_config(data: Vec<u8>, builder: HexViewBuilder<'static>) -> OwnedHexView {
+        OwnedHexView {
+            data,
+            config: builder.finish(),
+        }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns a borrowing view of the data.
+    pub fn view(&self) -> HexView<'_> {
+        let mut view = self.config.clone();
+        view.data = Fragments::new(&[&self.data]);
+        view
+    }
+}
+
+impl Display for OwnedHexView {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self.view())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use format::HexViewBuilder;
+
+    use super::*;
+
+    fn make_view() -> OwnedHexView {
+        let data: Vec<u8> = (0x40..0x40 + 6).collect();
+        OwnedHexView::with_config(data, HexViewBuilder::new(&[]).row_width(4))
+    }
+
+    #[test]
+    fn an_owned_view_can_be_returned_from_a_function() {
+        let data: Vec<u8> = (0x40..0x40 + 6).collect();
+        let expected = format!("{}", HexViewBuilder::new(&data).row_width(4).finish());
+
+        assert_eq!(expected, format!("{}", make_view()));
+    }
+
+    #[test]
+    fn an_owned_view_uses_the_default_configuration() {
+        let owned_view = OwnedHexView::new(vec![0x41, 0x42]);
+
+        assert_eq!(format!("{}", HexView::new(&[0x41, 0x42])), format!("{}", owned_view));
+    }
+}
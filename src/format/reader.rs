@@ -0,0 +1,239 @@
+use std::fmt::{Display, Formatter, Result};
+use std::io::{self, Read, Write};
+use std::ops::Range;
+
+use super::{Fragments, HexView, HexViewBuilder, RowDisplay};
+
+
+impl<'a> HexViewBuilder<'a> {
+    /// Dumps everything read from `reader` to `out` with the configuration of the builder, the
+    /// data of the builder is ignored.
+    ///
+    /// The reader is read [read_buffer_rows](#method.read_buffer_rows) rows at a time and the
+    /// output is the same for any buffer size. Every row is followed by a newline, squeezing and
+    /// the footers are not supported. Reading stops at the [limit](#method.limit).
+    ///
+    /// The options that depend on all of the data can't be streamed, they are rejected with an
+    /// `InvalidInput` error: width changes, gaps, a wide char panel, row numbers, percentages,
+    /// caret notation, and with color the color functions and the diff against the previous row.
+    pub fn dump_reader<R: Read, W: Write>(self, mut reader: R, out: &mut W) -> io::Result<()> {
+        let config = self.finish();
+
+        if config.row_width == 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Invalid HexView::width"));
+        }
+
+        if let Some(option) = unstreamable_option(&config) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{} can't be used with dump_reader", option)));
+        }
+
+        let row_span = config.row_width * config.stride;
+        let mut buffer = vec![0; std::cmp::max(1, config.read_buffer_rows) * row_span];
+        let mut pending = Vec::new();
+        let mut address = config.address_offset;
+        let mut first = true;
+        let mut remaining = config.limit.unwrap_or(usize::MAX);
+
+        while remaining != 0 {
+            let count = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(count) => std::cmp::min(count, remaining),
+                Err(ref error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+
+            remaining -= count;
+
+            pending.extend_from_slice(&buffer[..count]);
+
+            let begin_padding = if first { config.begin_padding_at(shown_address(&config, address)) } else { 0 };
+            let complete = ((begin_padding + pending.len()) / row_span * row_span).saturating_sub(begin_padding);
+
+            if complete != 0 {
+                write_rows(&config, out, &pending[..complete], address, first)?;
+                pending.drain(..complete);
+                address += complete;
+                first = false;
+            }
+        }
+
+        if first || !pending.is_empty() {
+            write_rows(&config, out, &pending, address, first)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes the rows of `data` at `address`, preceded by the title and header for the `first` rows.
+fn write_rows<W: Write>(config: &HexView, out: &mut W, data: &[u8], address: usize, first: bool) -> io::Result<()> {
+    let offset = address - config.address_offset;
+    let mut view = config.clone();
+    view.data = Fragments::new(&[data]);
+    view.limit = None;
+
+    // The offset based options are relative to the start of the whole stream.
+    for highlight in view.highlights.iter_mut() {
+        highlight.range = shifted(&highlight.range, offset);
+    }
+
+    view.focus = config.focus.iter().map(|range| shifted(range, offset)).collect();
+    view.underlines = config.underlines.iter().map(|range| shifted(range, offset)).collect();
+
+    if first {
+        write!(out, "{}", HeaderDisplay { view: &view })?;
+    }
+
+    view.relative_addresses = false;
+    view.address_offset = shown_address(config, address);
+
+    // The later rows continue the grid of the first ones, rather than being aligned on their own.
+    if !first {
+        view.align_addresses = false;
+    }
+
+    let view = view.laid_out();
+
+    for row in view.rows() {
+        writeln!(out, "{}", RowDisplay { view: &view, row })?;
+    }
+
+    Ok(())
+}
+
+/// Returns the name of an option of `config` that depends on all of the data.
+fn unstreamable_option(config: &HexView) -> Option<&'static str> {
+    let colored = |option: bool| config.use_color && option;
+
+    if !config.width_changes.is_empty() {
+        Some("width_change")
+    } else if !config.gaps.is_empty() {
+        Some("gap")
+    } else if config.char_row_width.is_some_and(|width| width > config.row_width) {
+        Some("char_row_width")
+    } else if config.show_row_numbers || config.show_hex_row_index {
+        Some("show_row_numbers")
+    } else if config.show_percentage {
+        Some("show_percentage")
+    } else if config.caret_notation {
+        Some("caret_notation")
+    } else if colored(config.color_fn.is_some() || config.nibble_color_fn.is_some()) {
+        Some("color_fn")
+    } else if colored(config.diff_against_previous) {
+        Some("diff_against_previous")
+    } else {
+        None
+    }
+}
+
+/// Returns `range` relative to `offset`, the part in front of it is dropped.
+fn shifted(range: &Range<usize>, offset: usize) -> Range<usize> {
+    range.start.saturating_sub(offset)..range.end.saturating_sub(offset)
+}
+
+/// Returns the address of the byte at `address` as it is printed.
+fn shown_address(config: &HexView, address: usize) -> usize {
+    if config.relative_addresses {
+        address - config.address_offset
+    } else {
+        address
+    }
+}
+
+/// Displays the title and header of a [HexView](struct.HexView.html).
+struct HeaderDisplay<'v, 'a: 'v> {
+    view: &'v HexView<'a>,
+}
+
+impl<'v, 'a> Display for HeaderDisplay<'v, 'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        if let Some(title) = self.view.title {
+            writeln!(f, "{}", title)?;
+        }
+
+        self.view.fmt_header(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use color::Color;
+    use format::HexViewBuilder;
+
+    #[test]
+    fn the_dump_does_not_depend_on_the_buffer_size() {
+        let data: Vec<u8> = (0..100).collect();
+        let mut small_buffer_dump = Vec::new();
+        let mut large_buffer_dump = Vec::new();
+
+        HexViewBuilder::new(&[])
+            .address_offset(3)
+            .row_width(8)
+            .read_buffer_rows(1)
+            .dump_reader(&data[..], &mut small_buffer_dump)
+            .unwrap();
+        HexViewBuilder::new(&[])
+            .address_offset(3)
+            .row_width(8)
+            .read_buffer_rows(8)
+            .dump_reader(&data[..], &mut large_buffer_dump)
+            .unwrap();
+
+        let view = HexViewBuilder::new(&data).address_offset(3).row_width(8).finish();
+
+        assert_eq!(small_buffer_dump, large_buffer_dump);
+        assert_eq!(format!("{}\n", view), String::from_utf8(small_buffer_dump).unwrap());
+    }
+
+    fn assert_dump_matches_view(builder: fn(&[u8]) -> HexViewBuilder) {
+        let data: Vec<u8> = (0x41..0x41 + 40).collect();
+        let mut dump = Vec::new();
+
+        builder(&[]).read_buffer_rows(1).dump_reader(&data[..], &mut dump).unwrap();
+
+        assert_eq!(format!("{}\n", builder(&data).finish()), String::from_utf8(dump).unwrap());
+    }
+
+    #[test]
+    fn the_limit_and_highlights_do_not_depend_on_the_buffer_size() {
+        let data: Vec<u8> = (0x41..0x41 + 40).collect();
+        let dump = |rows| {
+            let mut dump = Vec::new();
+
+            HexViewBuilder::new(&[])
+                .row_width(4)
+                .limit(22)
+                .use_color(true)
+                .highlight(10..18, Color::Red)
+                .read_buffer_rows(rows)
+                .dump_reader(&data[..], &mut dump)
+                .unwrap();
+
+            String::from_utf8(dump).unwrap()
+        };
+
+        let view = HexViewBuilder::new(&data[..22]).row_width(4).use_color(true).highlight(10..18, Color::Red).finish();
+
+        assert_eq!(dump(1), dump(8));
+        assert_eq!(format!("{}\n", view), dump(8));
+    }
+
+    #[test]
+    fn the_options_depending_on_all_data_are_rejected() {
+        let result = HexViewBuilder::new(&[]).show_row_numbers(true).dump_reader(&[0x41][..], &mut Vec::new());
+
+        assert_eq!(io::ErrorKind::InvalidInput, result.unwrap_err().kind());
+    }
+
+    #[test]
+    fn the_dump_matches_the_view_with_a_stride() {
+        assert_dump_matches_view(|data| HexViewBuilder::new(data).address_offset(3).row_width(4).stride(2));
+    }
+
+    #[test]
+    fn the_dump_matches_the_view_with_an_alignment() {
+        assert_dump_matches_view(|data| HexViewBuilder::new(data).address_offset(20).row_width(12).align_to(8));
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt::{Display, Formatter, Result};
+
+use super::{HexView, HexViewBuilder};
+
+
+/// Returns the header and payload length of the record at the start of the bytes, or `None` when
+/// the bytes don't start with a valid record.
+type ReadLenFn = fn(&[u8]) -> Option<(usize, usize)>;
+
+/// A record of a length-prefixed stream.
+enum Record<'a> {
+    Valid { header_len: usize, payload_len: usize, view: HexView<'a> },
+    Truncated { header_len: usize, payload_len: usize, view: HexView<'a> },
+    Malformed { view: HexView<'a> },
+}
+
+/// Displays the records of a length-prefixed stream, every record is dumped on its own below a
+/// label with its number and lengths. Created by [HexView::from_records](struct.HexView.html#method.from_records).
+pub struct RecordView<'a> {
+    records: Vec<Record<'a>>,
+}
+
+impl<'a> HexView<'a> {
+    /// Splits `data` into records, `read_len` is given the remaining bytes and returns the header
+    /// and payload length of the next record.
+    ///
+    /// When `read_len` returns `None` or an empty record, the remaining bytes are dumped as one
+    /// malformed record. A record that is longer than the remaining bytes is marked as truncated.
+    pub fn from_records(data: &'a [u8], read_len: ReadLenFn) -> RecordView<'a> {
+        let view = |start: usize, end: usize| HexViewBuilder::new(&data[start..end]).address_offset(start).finish();
+        let mut records = Vec::new();
+        let mut offset = 0;
+
+        while offset < data.len() {
+            let record = match read_len(&data[offset..]) {
+                Some((header_len, payload_len)) if header_len != 0 || payload_len != 0 => {
+                    let end = offset.checked_add(header_len).and_then(|end| end.checked_add(payload_len));
+
+                    match end {
+                        Some(end) if end <= data.len() => {
+                            Record::Valid { header_len, payload_len, view: view(offset, end) }
+                        }
+                        _ => Record::Truncated { header_len, payload_len, view: view(offset, data.len()) },
+                    }
+                }
+                _ => Record::Malformed { view: view(offset, data.len()) },
+            };
+
+            offset = match record {
+                Record::Valid { ref view, .. } => offset + view.data.len(),
+                _ => data.len(),
+            };
+            records.push(record);
+        }
+
+        RecordView { records }
+    }
+}
+
+impl<'a> Display for RecordView<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        for (index, record) in self.records.iter().enumerate() {
+            if index != 0 {
+                writeln!(f)?;
+            }
+
+            match *record {
+                Record::Valid { header_len, payload_len, ref view } => {
+                    write!(f, "-- record {}: {} + {} bytes --\n{}", index, header_len, payload_len, view)?
+                }
+                Record::Truncated { header_len, payload_len, ref view } => {
+                    write!(f, "-- record {}: {} + {} bytes, truncated --\n{}", index, header_len, payload_len, view)?
+                }
+                Record::Malformed { ref view } => write!(f, "-- record {}: malformed --\n{}", index, view)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_len(bytes: &[u8]) -> Option<(usize, usize)> {
+        bytes.first().map(|&len| (1, len as usize))
+    }
+
+    #[test]
+    fn the_records_are_separated_and_labeled() {
+        let data = [3, b'A', b'B', b'C', 2, b'D', b'E'];
+
+        let result = format!("{}", HexView::from_records(&data, read_len));
+        let mut lines = result.lines();
+
+        assert_eq!(Some("-- record 0: 1 + 3 bytes --"), lines.next());
+        assert!(lines.next().unwrap().starts_with("00000000  03 41 42 43    "));
+        assert_eq!(Some("-- record 1: 1 + 2 bytes --"), lines.next());
+        assert!(lines.next().unwrap().starts_with("00000000              02 44 45    "));
+        assert_eq!(None, lines.next());
+    }
+
+    #[test]
+    fn a_truncated_record_is_marked() {
+        let data = [1, b'A', 5, b'B'];
+
+        let result = format!("{}", HexView::from_records(&data, read_len));
+
+        assert_eq!(Some("-- record 1: 1 + 5 bytes, truncated --"), result.lines().nth(2));
+        assert_eq!(4, result.lines().count());
+    }
+
+    #[test]
+    fn a_record_longer_than_the_address_space_is_truncated() {
+        let data = [1, b'A'];
+
+        let result = format!("{}", HexView::from_records(&data, |_| Some((1, usize::MAX))));
+
+        assert_eq!(Some(format!("-- record 0: 1 + {} bytes, truncated --", usize::MAX).as_str()), result.lines().next());
+        assert_eq!(2, result.lines().count());
+    }
+}
@@ -37,10 +37,35 @@
 //! 00000040  40 41 42 43 44 45 46 47                          | @ABCDEFG         |
 //! ```
 
+#[macro_use]
+mod macros;
 mod byte_mapping;
+mod color;
+mod diff;
 mod format;
+mod parse;
+mod writer;
 
+pub use byte_mapping::as_char;
 pub use byte_mapping::CODEPAGE_0850;
+pub use byte_mapping::CODEPAGE_ASCII;
 pub use byte_mapping::CODEPAGE_1252;
+pub use byte_mapping::CODEPAGE_MAC_ROMAN;
+pub use byte_mapping::CODEPAGE_WINDOWS_1252;
+pub use byte_mapping::validate_single_width;
+pub use color::Color;
+pub use diff::{diff_files, interleave};
+pub use format::ChecksumKind;
+pub use format::Endian;
+pub use format::FieldView;
+pub use format::Highlight;
 pub use format::HexView;
 pub use format::HexViewBuilder;
+pub use format::MultiHexView;
+pub use format::OwnedHexView;
+pub use format::Position;
+pub use format::Radix;
+pub use format::RecordView;
+pub use parse::{parse_auto, DetectedConfig, Dialect, ParseError};
+pub use writer::DumpState;
+pub use writer::HexDumpWriter;
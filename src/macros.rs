@@ -0,0 +1,45 @@
+/// Formats anything implementing `AsRef<[u8]>` as a hex view.
+///
+/// The data can be followed by `key = value` pairs, every key is forwarded to the
+/// [HexViewBuilder](struct.HexViewBuilder.html) method with the same name. The `offset` key is
+/// accepted as a shorthand for `address_offset`.
+///
+/// ```rust
+/// # #[macro_use] extern crate hexplay;
+/// # fn main() {
+/// let dump = hexdump!(b"hexplay", row_width = 8, offset = 0x100);
+///
+/// assert_eq!("00000100  68 65 78 70 6C 61 79     | hexplay  |", dump);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! hexdump {
+    (@option $builder:expr, offset, $value:expr) => {
+        $builder.address_offset($value)
+    };
+    (@option $builder:expr, $option:ident, $value:expr) => {
+        $builder.$option($value)
+    };
+    ($data:expr $(, $option:ident = $value:expr)* $(,)*) => {{
+        let data: &[u8] = ::std::convert::AsRef::<[u8]>::as_ref(&$data);
+        let builder = $crate::HexViewBuilder::new(data);
+        $( let builder = hexdump!(@option builder, $option, $value); )*
+        format!("{}", builder.finish())
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn the_macro_formats_the_data() {
+        assert_eq!("00000000  41  | A |", hexdump!(&[0x41], row_width = 1));
+    }
+
+    #[test]
+    fn the_macro_accepts_owned_data_and_forwards_the_options() {
+        let data = vec![0xAB, 0xCD];
+
+        assert_eq!("000000fe     ab  |  ½ |\n00000100  cd     | ═  |",
+                   hexdump!(data, row_width = 2, offset = 0xFF, uppercase = false));
+    }
+}
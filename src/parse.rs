@@ -0,0 +1,213 @@
+use std::error::Error;
+use std::fmt;
+
+
+/// The format of a hex dump recognized by [parse_auto](fn.parse_auto.html).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dialect {
+    /// The default output of a [HexView](struct.HexView.html), `00000000  41 42  | AB |`.
+    Hexplay,
+    /// The output of `xxd`, `00000000: 4142  AB`.
+    Xxd,
+    /// The output of `hexdump -C`, `00000000  41 42  |AB|`.
+    HexdumpC,
+}
+
+/// The configuration inferred from a parsed dump.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DetectedConfig {
+    pub dialect: Dialect,
+    /// The address of the first byte.
+    pub address_offset: usize,
+    /// The largest number of bytes found on a row.
+    pub row_width: usize,
+}
+
+/// The error returned when a dump can't be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The 1-based number of the offending line.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// Decodes a hex dump in any of the supported [dialects](enum.Dialect.html), detected from the
+/// layout of its first data line.
+///
+/// Squeezed rows (`*`) are restored by repeating the previous row up to the next address, lines
+/// holding only an address (like the final offset of `hexdump -C`) are ignored.
+pub fn parse_auto(dump: &str) -> Result<(Vec<u8>, DetectedConfig), ParseError> {
+    let mut bytes = Vec::new();
+    let mut config: Option<DetectedConfig> = None;
+    let mut previous_row: Vec<u8> = Vec::new();
+    let mut squeezed = false;
+
+    for (index, line) in dump.lines().enumerate() {
+        let error = |message: &str| ParseError { line: index + 1, message: message.to_string() };
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        if line.trim() == "*" {
+            squeezed = true;
+            continue;
+        }
+
+        let (address_text, rest) = match line.find(' ') {
+            Some(position) => line.split_at(position),
+            None => (line, ""),
+        };
+        let address = usize::from_str_radix(address_text.trim_end_matches(':'), 16)
+            .map_err(|_| error("invalid address"))?;
+        let dialect = match config {
+            Some(config) => config.dialect,
+            None => detect_dialect(address_text, rest),
+        };
+
+        let (row, leading_columns) = parse_row(dialect, rest).ok_or_else(|| error("invalid hex byte"))?;
+
+        let config = config.get_or_insert(DetectedConfig {
+            dialect,
+            address_offset: address + leading_columns,
+            row_width: 0,
+        });
+
+        if squeezed {
+            while !previous_row.is_empty() && config.address_offset + bytes.len() < address {
+                bytes.extend_from_slice(&previous_row);
+            }
+
+            squeezed = false;
+        }
+
+        config.row_width = config.row_width.max(leading_columns + row.len());
+        bytes.extend_from_slice(&row);
+        previous_row = row;
+    }
+
+    config
+        .map(|config| (bytes, config))
+        .ok_or(ParseError { line: 0, message: "no data found".to_string() })
+}
+
+fn detect_dialect(address_text: &str, rest: &str) -> Dialect {
+    if address_text.ends_with(':') {
+        return Dialect::Xxd;
+    }
+
+    let panel = match rest.find('|') {
+        Some(position) => rest[position..].trim_end(),
+        None => return Dialect::HexdumpC,
+    };
+
+    // A Hexplay char panel is enclosed in `| ` and ` |`, a `hexdump -C` one in `|` only.
+    if panel.len() >= 4 && panel.starts_with("| ") && panel.ends_with(" |") {
+        Dialect::Hexplay
+    } else {
+        Dialect::HexdumpC
+    }
+}
+
+/// Returns the bytes of the hex section of a line following the address, and the number of
+/// padding columns in front of them.
+fn parse_row(dialect: Dialect, rest: &str) -> Option<(Vec<u8>, usize)> {
+    let (hex, leading_columns) = match dialect {
+        Dialect::Xxd => {
+            let hex = rest.trim_start();
+            (&hex[..hex.find("  ").unwrap_or(hex.len())], 0)
+        }
+        Dialect::Hexplay => {
+            let hex = rest.get(2..).unwrap_or("");
+            let hex = &hex[..hex.find('|').unwrap_or(hex.len())];
+            (hex, (hex.len() - hex.trim_start().len()) / 3)
+        }
+        Dialect::HexdumpC => (&rest[..rest.find('|').unwrap_or(rest.len())], 0),
+    };
+
+    let mut bytes = Vec::new();
+
+    for token in hex.split_whitespace() {
+        if token.len() % 2 != 0 || !token.is_ascii() {
+            return None;
+        }
+
+        for index in (0..token.len()).step_by(2) {
+            bytes.push(u8::from_str_radix(&token[index..index + 2], 16).ok()?);
+        }
+    }
+
+    Some((bytes, leading_columns))
+}
+
+#[cfg(test)]
+mod tests {
+    use format::HexViewBuilder;
+
+    use super::*;
+
+    #[test]
+    fn xxd_and_hexplay_lines_decode_to_the_same_bytes() {
+        let xxd = "00000010: 4142 4344 45                             ABCDE";
+        let hexplay = "00000010  41 42 43 44 45                                    | ABCDE            |";
+
+        let (xxd_bytes, xxd_config) = parse_auto(xxd).unwrap();
+        let (hexplay_bytes, hexplay_config) = parse_auto(hexplay).unwrap();
+
+        assert_eq!(vec![0x41, 0x42, 0x43, 0x44, 0x45], xxd_bytes);
+        assert_eq!(xxd_bytes, hexplay_bytes);
+        assert_eq!(Dialect::Xxd, xxd_config.dialect);
+        assert_eq!(Dialect::Hexplay, hexplay_config.dialect);
+        assert_eq!(0x10, hexplay_config.address_offset);
+    }
+
+    #[test]
+    fn the_begin_padding_of_a_hexplay_dump_is_skipped() {
+        let view = HexViewBuilder::new(&[0x42, 0x43, 0x44]).address_offset(1).row_width(4).finish();
+
+        let (bytes, config) = parse_auto(&format!("{}", view)).unwrap();
+
+        assert_eq!(vec![0x42, 0x43, 0x44], bytes);
+        assert_eq!(1, config.address_offset);
+        assert_eq!(4, config.row_width);
+    }
+
+    #[test]
+    fn squeezed_hexdump_rows_are_restored() {
+        let dump = "00000000  00 00 00 00  |....|\n*\n0000000c  01                |.|\n0000000d";
+
+        let (bytes, config) = parse_auto(dump).unwrap();
+
+        assert_eq!(Dialect::HexdumpC, config.dialect);
+        assert_eq!(vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1], bytes);
+    }
+
+    #[test]
+    fn a_hexdump_row_with_a_leading_space_in_the_char_panel_is_detected() {
+        let short_row = "00000000  20 41  | A|";
+        let full_row = "00000000  20 41 42 43 44 45 46 47  48 49 4a 4b 4c 4d 4e 4f  | ABCDEFGHIJKLMNO|";
+
+        let (short_bytes, short_config) = parse_auto(short_row).unwrap();
+        let (full_bytes, full_config) = parse_auto(full_row).unwrap();
+
+        assert_eq!(Dialect::HexdumpC, short_config.dialect);
+        assert_eq!(vec![0x20, 0x41], short_bytes);
+        assert_eq!(Dialect::HexdumpC, full_config.dialect);
+        assert_eq!(16, full_bytes.len());
+    }
+
+    #[test]
+    fn an_invalid_byte_is_reported_with_its_line() {
+        let dump = "00000000  41 42  | AB |\n00000002  4G     | A  |";
+
+        assert_eq!(2, parse_auto(dump).unwrap_err().line);
+    }
+}
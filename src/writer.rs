@@ -0,0 +1,249 @@
+use std::cmp;
+use std::io::{self, Write};
+
+use format::HexViewBuilder;
+
+
+/// The progress of a dump that is rendered piecewise, so a caller can feed the data in chunks
+/// of any size and pick up exactly where the last call left off.
+///
+/// Only complete rows are dumped, the bytes of an incomplete row are kept in `leftover` until
+/// they are completed by a later chunk or the dump is [finished](#method.finish).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DumpState {
+    /// The address of the first byte of `leftover`.
+    pub address: u64,
+    /// The bytes of the incomplete row that was not dumped yet.
+    pub leftover: Vec<u8>,
+}
+
+impl DumpState {
+    /// Creates the state of a dump that starts at `address`.
+    pub fn new(address: u64) -> DumpState {
+        DumpState {
+            address,
+            leftover: Vec::new(),
+        }
+    }
+
+    /// Dumps the complete rows of the leftover and `chunk` to `out`, and returns the state to
+    /// continue with.
+    pub fn dump_chunk<D: Write>(mut self, out: &mut D, chunk: &[u8], row_width: usize) -> io::Result<DumpState> {
+        self.push(out, chunk, row_width)?;
+        Ok(self)
+    }
+
+    /// Dumps the final incomplete row, padded on the right.
+    pub fn finish<D: Write>(mut self, out: &mut D, row_width: usize) -> io::Result<()> {
+        self.dump_leftover(out, row_width)
+    }
+
+    fn row_capacity(&self, row_width: usize) -> usize {
+        row_width - (self.address % row_width as u64) as usize
+    }
+
+    fn push<D: Write>(&mut self, out: &mut D, mut chunk: &[u8], row_width: usize) -> io::Result<()> {
+        if row_width == 0 {
+            return Ok(());
+        }
+
+        while !chunk.is_empty() {
+            let count = cmp::min(self.row_capacity(row_width) - self.leftover.len(), chunk.len());
+            self.leftover.extend_from_slice(&chunk[..count]);
+            chunk = &chunk[count..];
+
+            if self.leftover.len() == self.row_capacity(row_width) {
+                self.dump_leftover(out, row_width)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn dump_leftover<D: Write>(&mut self, out: &mut D, row_width: usize) -> io::Result<()> {
+        if self.leftover.is_empty() {
+            return Ok(());
+        }
+
+        let view = HexViewBuilder::new(&self.leftover)
+            .address_offset(self.address as usize)
+            .row_width(row_width)
+            .finish();
+
+        writeln!(out, "{}", view)?;
+        self.address += self.leftover.len() as u64;
+        self.leftover.clear();
+
+        Ok(())
+    }
+}
+
+/// A [Write](https://doc.rust-lang.org/std/io/trait.Write.html) adapter that forwards everything
+/// to an inner writer while writing a hex dump of the same bytes to a second sink.
+///
+/// Bytes are dumped one row at a time, an incomplete row is held back until it is completed by a
+/// later write or until the writer is flushed or dropped.
+///
+/// A failing dump doesn't fail the writes to the inner writer, the dump stops and the error is
+/// returned by the next [flush](#method.flush).
+pub struct HexDumpWriter<W: Write, D: Write> {
+    inner: W,
+    dump: D,
+    state: DumpState,
+    row_width: usize,
+    dump_error: Option<io::Error>,
+    dump_failed: bool,
+}
+
+impl<W: Write> HexDumpWriter<W, io::Stderr> {
+    /// Creates a writer that dumps everything written to `inner` on stderr.
+    pub fn to_stderr(inner: W) -> HexDumpWriter<W, io::Stderr> {
+        HexDumpWriter::new(inner, io::stderr())
+    }
+}
+
+impl<W: Write, D: Write> HexDumpWriter<W, D> {
+    pub fn new(inner: W, dump: D) -> HexDumpWriter<W, D> {
+        HexDumpWriter {
+            inner,
+            dump,
+            state: DumpState::new(0),
+            row_width: 16,
+            dump_error: None,
+            dump_failed: false,
+        }
+    }
+
+    pub fn row_width(mut self, width: usize) -> HexDumpWriter<W, D> {
+        self.row_width = width;
+        self
+    }
+
+    fn fail_dump(&mut self, error: io::Error) {
+        self.dump_failed = true;
+        self.dump_error = Some(error);
+    }
+}
+
+impl<W: Write, D: Write> Write for HexDumpWriter<W, D> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+
+        if !self.dump_failed {
+            if let Err(error) = self.state.push(&mut self.dump, &buf[..written], self.row_width) {
+                self.fail_dump(error);
+            }
+        }
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.dump_failed {
+            if let Err(error) = self.state.dump_leftover(&mut self.dump, self.row_width).and_then(|_| self.dump.flush()) {
+                self.fail_dump(error);
+            }
+        }
+
+        self.inner.flush()?;
+
+        match self.dump_error.take() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<W: Write, D: Write> Drop for HexDumpWriter<W, D> {
+    /// Dumps the incomplete last row, errors are ignored.
+    fn drop(&mut self) {
+        if !self.dump_failed {
+            let _ = self.state.dump_leftover(&mut self.dump, self.row_width);
+            let _ = self.dump.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_written_in_several_calls_are_dumped_as_one_block() {
+        let data: Vec<u8> = (0x40..0x40 + 20).collect();
+        let mut inner = Vec::new();
+        let mut dump = Vec::new();
+
+        {
+            let mut writer = HexDumpWriter::new(&mut inner, &mut dump);
+            writer.write_all(&data[..12]).unwrap();
+            writer.write_all(&data[12..]).unwrap();
+            writer.flush().unwrap();
+        }
+
+        let expected = format!("{}\n", HexViewBuilder::new(&data).finish());
+
+        assert_eq!(inner, data);
+        assert_eq!(String::from_utf8(dump).unwrap(), expected);
+    }
+
+    #[test]
+    fn a_partial_row_is_dumped_when_dropped() {
+        let mut dump = Vec::new();
+
+        {
+            let mut writer = HexDumpWriter::new(io::sink(), &mut dump);
+            writer.write_all(&[0x41, 0x42, 0x43]).unwrap();
+        }
+
+        let expected = format!("{}\n", HexViewBuilder::new(&[0x41, 0x42, 0x43]).finish());
+
+        assert_eq!(String::from_utf8(dump).unwrap(), expected);
+    }
+
+    struct FailingSink;
+
+    impl Write for FailingSink {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("dump failed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn a_failing_dump_does_not_fail_the_writes() {
+        let data: Vec<u8> = (0x40..0x40 + 40).collect();
+        let mut inner = Vec::new();
+
+        {
+            let mut writer = HexDumpWriter::new(&mut inner, FailingSink);
+
+            assert_eq!(writer.write(&data).unwrap(), 40);
+            assert!(writer.flush().is_err());
+            assert!(writer.flush().is_ok());
+        }
+
+        assert_eq!(inner, data);
+    }
+
+    #[test]
+    fn a_dump_fed_in_chunks_matches_the_one_shot_dump() {
+        let data: Vec<u8> = (0x40..0x40 + 10).collect();
+        let mut dump = Vec::new();
+        let mut state = DumpState::new(0);
+
+        for chunk in data.chunks(3) {
+            state = state.dump_chunk(&mut dump, chunk, 4).unwrap();
+        }
+
+        assert_eq!(state, DumpState { address: 8, leftover: vec![0x48, 0x49] });
+        state.finish(&mut dump, 4).unwrap();
+
+        let expected = format!("{}\n", HexViewBuilder::new(&data).row_width(4).finish());
+
+        assert_eq!(String::from_utf8(dump).unwrap(), expected);
+    }
+}